@@ -13,6 +13,8 @@ Usage (standalone):
 
 Usage (called by qwen-tts Rust CLI):
     The Rust binary invokes this script with the same flags when backend != mlx.
+    With `keep_warm` set, it instead starts the script with `--serve SOCKET`
+    and sends requests over that socket while the model stays loaded.
 
 # TODO: Update model loading once Qwen3-TTS transformers integration is finalized.
 #       The Qwen3-TTS model class names and generate API may change as upstream
@@ -439,8 +441,8 @@ def _build_parser() -> argparse.ArgumentParser:
     parser.add_argument(
         "--text",
         type=str,
-        required=True,
-        help="Text to synthesise",
+        default=None,
+        help="Text to synthesise (required unless --serve is given)",
     )
     parser.add_argument(
         "--voice",
@@ -484,12 +486,112 @@ def _build_parser() -> argparse.ArgumentParser:
         default="output.wav",
         help="Output WAV file path (default: output.wav)",
     )
+    parser.add_argument(
+        "--serve",
+        type=str,
+        default=None,
+        metavar="SOCKET",
+        help="Keep the model loaded and answer requests on this Unix socket",
+    )
+    parser.add_argument(
+        "--keep_warm",
+        type=float,
+        default=60.0,
+        help="With --serve, exit after this many idle seconds (default: 60)",
+    )
     return parser
 
 
+# ---------------------------------------------------------------------------
+# Warm server (keep_warm)
+# ---------------------------------------------------------------------------
+
+def _generate_to_file(model, processor, request: dict, device: torch.device) -> None:
+    """Generate one request's audio and save it to its output path."""
+    ref_audio: np.ndarray | None = None
+    if request.get("ref_audio"):
+        if not os.path.isfile(request["ref_audio"]):
+            raise FileNotFoundError(f"Reference audio file not found: {request['ref_audio']}")
+        ref_audio = _load_ref_audio(request["ref_audio"])
+
+    print(f"[tts] Generating speech ({len(request['text'])} chars) ...", flush=True)
+    audio = _generate_speech(
+        model,
+        processor,
+        text=request["text"],
+        instruct=request.get("instruct"),
+        voice=request.get("voice"),
+        speed=float(request.get("speed", 1.0)),
+        ref_audio=ref_audio,
+        ref_text=request.get("ref_text"),
+        device=device,
+    )
+    _save_wav(request["output_path"], audio)
+    print(f"[done] Saved to {request['output_path']}", flush=True)
+
+
+def _serve(path: str, idle_secs: float, model, processor, device: torch.device) -> None:
+    """
+    Answer generation requests on a Unix socket until none arrives for
+    `idle_secs`. Each connection sends one JSON request line and receives one
+    JSON reply line: {"ok": true} or {"ok": false, "error": "..."}.
+    """
+    import json
+    import socket
+
+    # Another process may have started serving the same model meanwhile
+    probe = socket.socket(socket.AF_UNIX, socket.SOCK_STREAM)
+    try:
+        probe.connect(path)
+        print(f"[serve] {path} is already being served", flush=True)
+        return
+    except OSError:
+        pass
+    finally:
+        probe.close()
+
+    try:
+        os.unlink(path)
+    except FileNotFoundError:
+        pass
+    server = socket.socket(socket.AF_UNIX, socket.SOCK_STREAM)
+    server.bind(path)
+    server.listen()
+    server.settimeout(idle_secs)
+    print(f"[serve] Ready on {path}, idle timeout {idle_secs:g}s", flush=True)
+    try:
+        while True:
+            try:
+                conn, _ = server.accept()
+            except socket.timeout:
+                print("[serve] Idle, shutting down", flush=True)
+                break
+            with conn:
+                conn.settimeout(None)
+                try:
+                    request = json.loads(conn.makefile("r", encoding="utf-8").readline())
+                    _generate_to_file(model, processor, request, device)
+                    reply = {"ok": True}
+                except (Exception, SystemExit) as exc:
+                    print(f"ERROR: {exc}", file=sys.stderr, flush=True)
+                    reply = {"ok": False, "error": str(exc)}
+                try:
+                    conn.sendall((json.dumps(reply) + "\n").encode("utf-8"))
+                except OSError:
+                    pass
+    finally:
+        server.close()
+        try:
+            os.unlink(path)
+        except OSError:
+            pass
+
+
 def main() -> None:
     parser = _build_parser()
     args = parser.parse_args()
+    if args.text is None and args.serve is None:
+        parser.error("--text is required")
 
     # ------------------------------------------------------------------
     # Device
@@ -503,6 +605,10 @@ def main() -> None:
     model, processor = _load_model_and_processor(args.model, device)
     model.eval()
 
+    if args.serve:
+        _serve(args.serve, args.keep_warm, model, processor, device)
+        return
+
     # ------------------------------------------------------------------
     # Reference audio (voice cloning)
     # ------------------------------------------------------------------
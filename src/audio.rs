@@ -0,0 +1,845 @@
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use anyhow::{Context, Result};
+use serde::Serialize;
+
+use crate::error::ErrorKind;
+use crate::platform;
+
+/// Output formats produced by converting the backend's WAV with ffmpeg.
+pub const TRANSCODE_FORMATS: &[&str] = &["mp3", "flac", "ogg", "opus", "m4a"];
+
+/// Sample rate the Qwen3-TTS models generate at, in Hz.
+pub const NATIVE_SAMPLE_RATE: u32 = 24_000;
+
+/// Approximate bytes for `secs` of mono speech in format `ext` at `rate` Hz.
+/// Lossy formats use ffmpeg's default bitrates, which don't depend on the
+/// rate; FLAC typically compresses speech to about 60% of the WAV size.
+pub fn estimated_size(ext: &str, rate: u32, secs: f64) -> u64 {
+    let wav_bytes_per_sec = f64::from(rate) * 2.0;
+    let bytes_per_sec = match ext {
+        "flac" => wav_bytes_per_sec * 0.6,
+        "mp3" | "m4a" => 16_000.0,
+        "ogg" => 14_000.0,
+        "opus" => 12_000.0,
+        _ => wav_bytes_per_sec,
+    };
+    (secs * bytes_per_sec) as u64
+}
+
+/// Lowercased extension of `path`, if any.
+pub fn extension(path: &Path) -> Option<String> {
+    path.extension().map(|e| e.to_string_lossy().to_lowercase())
+}
+
+/// Sample rates accepted for output resampling, in Hz.
+pub const SAMPLE_RATE_RANGE: std::ops::RangeInclusive<u32> = 8_000..=192_000;
+
+/// Check that the extension of a requested output path is a format we can
+/// write. Paths without an extension keep the backend's directory layout.
+pub fn validate_output_format(path: &Path) -> Result<()> {
+    match extension(path) {
+        None => Ok(()),
+        Some(ext) => validate_format(&ext),
+    }
+}
+
+/// Check that `ext` (without the dot) names a format we can write.
+pub fn validate_format(ext: &str) -> Result<()> {
+    if ext == "wav" || TRANSCODE_FORMATS.contains(&ext) {
+        return Ok(());
+    }
+    Err(ErrorKind::Config.error(format!(
+        "unsupported output format: .{ext} (expected .wav or one of: {})",
+        TRANSCODE_FORMATS
+            .iter()
+            .map(|f| format!(".{f}"))
+            .collect::<Vec<_>>()
+            .join(", ")
+    )))
+}
+
+/// Check that `rate` is a sample rate we resample outputs to.
+pub fn validate_sample_rate(rate: u32) -> Result<()> {
+    if SAMPLE_RATE_RANGE.contains(&rate) {
+        return Ok(());
+    }
+    Err(ErrorKind::Config.error(format!(
+        "unsupported sample rate: {rate} Hz (expected {}-{} Hz)",
+        SAMPLE_RATE_RANGE.start(),
+        SAMPLE_RATE_RANGE.end()
+    )))
+}
+
+/// Whether writing `path` needs a conversion from WAV.
+pub fn needs_transcode(path: &Path) -> bool {
+    extension(path).is_some_and(|ext| TRANSCODE_FORMATS.contains(&ext.as_str()))
+}
+
+/// Convert `src` into `dest` with ffmpeg; the format follows `dest`'s extension.
+pub fn transcode(src: &Path, dest: &Path) -> Result<()> {
+    if platform::find_in_path("ffmpeg").is_none() {
+        return Err(ErrorKind::Backend.error(format!(
+            "ffmpeg is required to write .{} files; install it or use a .wav output",
+            extension(dest).unwrap_or_default()
+        )));
+    }
+
+    let status = Command::new("ffmpeg")
+        .args(["-y", "-hide_banner", "-loglevel", "error", "-i"])
+        .arg(src)
+        .arg(dest)
+        .status()
+        .context("failed to run ffmpeg")?;
+
+    if !status.success() {
+        std::fs::remove_file(dest).ok();
+        return Err(ErrorKind::Backend.error(format!("ffmpeg failed to convert {}", src.display())));
+    }
+    Ok(())
+}
+
+/// Concatenate WAV files into a single file at `dest`, inserting
+/// `gap_after_ms[i]` milliseconds of silence after input `i` (missing entries
+/// mean no gap). With `crossfade_ms`, inputs joined without a gap overlap by
+/// up to that long with an equal-power fade. All inputs must share the same
+/// channel count, sample rate and sample format.
+pub fn concat_wavs(
+    inputs: &[PathBuf],
+    gap_after_ms: &[u32],
+    crossfade_ms: u32,
+    dest: &Path,
+) -> Result<()> {
+    let first = inputs.first().context("no audio segments to concatenate")?;
+    let spec = hound::WavReader::open(first)
+        .with_context(|| format!("failed to open {}", first.display()))?
+        .spec();
+    let channels = usize::from(spec.channels);
+    let fade_frames = (u64::from(spec.sample_rate) * u64::from(crossfade_ms) / 1000) as usize;
+
+    let mut writer = hound::WavWriter::create(dest, spec)
+        .with_context(|| format!("failed to create {}", dest.display()))?;
+
+    // End of the previous input, held back to overlap with the next one
+    let mut held: Vec<f64> = Vec::new();
+    for (i, input) in inputs.iter().enumerate() {
+        let mut reader = hound::WavReader::open(input)
+            .with_context(|| format!("failed to open {}", input.display()))?;
+        if reader.spec() != spec {
+            anyhow::bail!(
+                "cannot concatenate {}: audio format differs from {}",
+                input.display(),
+                first.display()
+            );
+        }
+        let gap = gap_after_ms.get(i).copied().unwrap_or(0);
+
+        if fade_frames == 0 {
+            match spec.sample_format {
+                hound::SampleFormat::Float => {
+                    for s in reader.samples::<f32>() {
+                        writer.write_sample(s?)?;
+                    }
+                }
+                hound::SampleFormat::Int => {
+                    for s in reader.samples::<i32>() {
+                        writer.write_sample(s?)?;
+                    }
+                }
+            }
+            write_silence(&mut writer, spec, gap)?;
+            continue;
+        }
+
+        let samples = read_normalized(&mut reader)?;
+        let frames = samples.len() / channels;
+
+        // Never let a fade cover more than half of a short input
+        let overlap = (held.len() / channels).min(frames / 2);
+        let (unmixed, tail) = held.split_at(held.len() - overlap * channels);
+        write_normalized(&mut writer, spec, unmixed)?;
+        let mixed: Vec<f64> = tail
+            .iter()
+            .zip(&samples)
+            .enumerate()
+            .map(|(n, (&out, &inc))| {
+                let t = ((n / channels) as f64 + 0.5) / overlap as f64;
+                let angle = t * std::f64::consts::FRAC_PI_2;
+                out * angle.cos() + inc * angle.sin()
+            })
+            .collect();
+        write_normalized(&mut writer, spec, &mixed)?;
+
+        let hold = if gap == 0 && i + 1 < inputs.len() {
+            fade_frames.min(frames / 2).min(frames - overlap)
+        } else {
+            0
+        };
+        let split = (frames - hold) * channels;
+        write_normalized(&mut writer, spec, &samples[overlap * channels..split])?;
+        held = samples[split..].to_vec();
+        write_silence(&mut writer, spec, gap)?;
+    }
+    write_normalized(&mut writer, spec, &held)?;
+
+    writer
+        .finalize()
+        .with_context(|| format!("failed to write {}", dest.display()))
+}
+
+/// Silence between clips when several references are merged into one.
+pub const REFERENCE_GAP_MS: u32 = 300;
+
+/// Merge several reference clips into one 16-bit mono WAV at the first
+/// clip's sample rate, separated by `REFERENCE_GAP_MS` of silence. Unlike
+/// `concat_wavs`, the clips may differ in rate, channels and sample format.
+pub fn merge_references(inputs: &[PathBuf], dest: &Path) -> Result<()> {
+    let first = inputs.first().context("no reference clips to merge")?;
+    let rate = sample_rate(first)?;
+    let spec = hound::WavSpec {
+        channels: 1,
+        sample_rate: rate,
+        bits_per_sample: 16,
+        sample_format: hound::SampleFormat::Int,
+    };
+    let mut writer = hound::WavWriter::create(dest, spec)
+        .with_context(|| format!("failed to create {}", dest.display()))?;
+
+    for (i, input) in inputs.iter().enumerate() {
+        // Bring every clip to the common rate first, through a scratch file
+        let resampled = dest.with_extension(format!("{i}.wav"));
+        let source = if sample_rate(input)? == rate {
+            input.clone()
+        } else {
+            resample(input, &resampled, rate)?;
+            resampled.clone()
+        };
+        let result = hound::WavReader::open(&source)
+            .with_context(|| format!("failed to open {}", source.display()))
+            .and_then(|mut reader| {
+                let channels = usize::from(reader.spec().channels);
+                let samples = read_normalized(&mut reader)?;
+                Ok(samples
+                    .chunks(channels)
+                    .map(|frame| frame.iter().sum::<f64>() / channels as f64)
+                    .collect::<Vec<f64>>())
+            });
+        std::fs::remove_file(&resampled).ok();
+        write_normalized(&mut writer, spec, &result?)?;
+        if i + 1 < inputs.len() {
+            write_silence(&mut writer, spec, REFERENCE_GAP_MS)?;
+        }
+    }
+
+    writer
+        .finalize()
+        .with_context(|| format!("failed to write {}", dest.display()))
+}
+
+/// Read every sample of a WAV file as a float in -1.0..=1.0.
+fn read_normalized<R: std::io::Read>(reader: &mut hound::WavReader<R>) -> Result<Vec<f64>> {
+    let spec = reader.spec();
+    Ok(match spec.sample_format {
+        hound::SampleFormat::Float => reader
+            .samples::<f32>()
+            .map(|s| s.map(f64::from))
+            .collect::<Result<_, _>>()?,
+        hound::SampleFormat::Int => {
+            let scale = (1i64 << (spec.bits_per_sample - 1)) as f64;
+            reader
+                .samples::<i32>()
+                .map(|s| s.map(|s| f64::from(s) / scale))
+                .collect::<Result<_, _>>()?
+        }
+    })
+}
+
+/// Write samples produced by `read_normalized` in the writer's format.
+fn write_normalized<W>(
+    writer: &mut hound::WavWriter<W>,
+    spec: hound::WavSpec,
+    samples: &[f64],
+) -> Result<()>
+where
+    W: std::io::Write + std::io::Seek,
+{
+    // Same scale as `read_normalized`, so untouched samples round-trip
+    // exactly; f64 holds every 32-bit integer sample
+    let scale = (1i64 << (spec.bits_per_sample - 1)) as f64;
+    for &value in samples {
+        match spec.sample_format {
+            hound::SampleFormat::Float => writer.write_sample(value as f32)?,
+            hound::SampleFormat::Int => {
+                writer.write_sample((value * scale).round().clamp(-scale, scale - 1.0) as i32)?
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Append `ms` milliseconds of silence at the writer's sample rate.
+fn write_silence<W>(writer: &mut hound::WavWriter<W>, spec: hound::WavSpec, ms: u32) -> Result<()>
+where
+    W: std::io::Write + std::io::Seek,
+{
+    let frames = u64::from(spec.sample_rate) * u64::from(ms) / 1000;
+    for _ in 0..frames * u64::from(spec.channels) {
+        match spec.sample_format {
+            hound::SampleFormat::Float => writer.write_sample(0.0f32)?,
+            hound::SampleFormat::Int => writer.write_sample(0i32)?,
+        }
+    }
+    Ok(())
+}
+
+/// Add `start_ms` / `end_ms` of silence around a WAV file, in place.
+pub fn pad_silence(path: &Path, start_ms: u32, end_ms: u32) -> Result<()> {
+    let mut reader = hound::WavReader::open(path)
+        .with_context(|| format!("failed to open {}", path.display()))?;
+    let spec = reader.spec();
+    let tmp = path.with_extension("padding.wav");
+    let mut writer = hound::WavWriter::create(&tmp, spec)
+        .with_context(|| format!("failed to create {}", tmp.display()))?;
+
+    write_silence(&mut writer, spec, start_ms)?;
+    match spec.sample_format {
+        hound::SampleFormat::Float => {
+            for s in reader.samples::<f32>() {
+                writer.write_sample(s?)?;
+            }
+        }
+        hound::SampleFormat::Int => {
+            for s in reader.samples::<i32>() {
+                writer.write_sample(s?)?;
+            }
+        }
+    }
+    write_silence(&mut writer, spec, end_ms)?;
+    writer
+        .finalize()
+        .with_context(|| format!("failed to write {}", tmp.display()))?;
+
+    std::fs::rename(&tmp, path).with_context(|| format!("failed to replace {}", path.display()))
+}
+
+/// Cut a WAV file down to at most `secs` seconds, in place.
+pub fn truncate(path: &Path, secs: f32) -> Result<()> {
+    let mut reader = hound::WavReader::open(path)
+        .with_context(|| format!("failed to open {}", path.display()))?;
+    let spec = reader.spec();
+    let max_frames = (f64::from(secs) * f64::from(spec.sample_rate)).round() as u64;
+    if u64::from(reader.duration()) <= max_frames {
+        return Ok(());
+    }
+    let max_samples = (max_frames * u64::from(spec.channels)) as usize;
+
+    let tmp = path.with_extension("trim.wav");
+    let mut writer = hound::WavWriter::create(&tmp, spec)
+        .with_context(|| format!("failed to create {}", tmp.display()))?;
+    match spec.sample_format {
+        hound::SampleFormat::Float => {
+            for s in reader.samples::<f32>().take(max_samples) {
+                writer.write_sample(s?)?;
+            }
+        }
+        hound::SampleFormat::Int => {
+            for s in reader.samples::<i32>().take(max_samples) {
+                writer.write_sample(s?)?;
+            }
+        }
+    }
+    writer
+        .finalize()
+        .with_context(|| format!("failed to write {}", tmp.display()))?;
+
+    std::fs::rename(&tmp, path).with_context(|| format!("failed to replace {}", path.display()))
+}
+
+/// Duration of a WAV file in seconds.
+pub fn duration_secs(path: &Path) -> Result<f64> {
+    let reader = hound::WavReader::open(path)
+        .with_context(|| format!("failed to open {}", path.display()))?;
+    let spec = reader.spec();
+    Ok(f64::from(reader.duration()) / f64::from(spec.sample_rate))
+}
+
+/// SHA-256 of a clip's audio, as hex. WAV files are hashed by format and
+/// samples, so copies that differ only in header chunks still match; other
+/// files are hashed as bytes.
+pub fn content_hash(path: &Path) -> Result<String> {
+    use sha2::{Digest, Sha256};
+
+    let mut hasher = Sha256::new();
+    match hound::WavReader::open(path) {
+        Ok(mut reader) => {
+            let spec = reader.spec();
+            hasher.update(format!(
+                "{}:{}:{}:",
+                spec.sample_rate, spec.channels, spec.bits_per_sample
+            ));
+            match spec.sample_format {
+                hound::SampleFormat::Float => {
+                    for s in reader.samples::<f32>() {
+                        hasher.update(s?.to_le_bytes());
+                    }
+                }
+                hound::SampleFormat::Int => {
+                    for s in reader.samples::<i32>() {
+                        hasher.update(s?.to_le_bytes());
+                    }
+                }
+            }
+        }
+        Err(_) => hasher.update(
+            std::fs::read(path).with_context(|| format!("failed to read {}", path.display()))?,
+        ),
+    }
+    Ok(format!("{:x}", hasher.finalize()))
+}
+
+/// INFO fields recording tools use for the spoken text, in order of
+/// preference: comment, then subject.
+const TRANSCRIPT_INFO_IDS: &[&[u8; 4]] = &[b"ICMT", b"ISBJ"];
+
+/// Largest `LIST` chunk read when looking for a transcript.
+const MAX_INFO_BYTES: u32 = 64 * 1024;
+
+/// Transcript embedded in a WAV's `LIST`/`INFO` chunk, if any. Files that
+/// aren't WAVs or have no such text give `None`.
+pub fn embedded_transcript(path: &Path) -> Option<String> {
+    use std::io::{Read, Seek, SeekFrom};
+
+    let mut file = std::fs::File::open(path).ok()?;
+    let mut header = [0u8; 12];
+    file.read_exact(&mut header).ok()?;
+    if &header[0..4] != b"RIFF" || &header[8..12] != b"WAVE" {
+        return None;
+    }
+    let mut chunk = [0u8; 8];
+    while file.read_exact(&mut chunk).is_ok() {
+        let size = u32::from_le_bytes(chunk[4..8].try_into().unwrap());
+        // Chunks are padded to an even length
+        let padded = i64::from(size) + i64::from(size & 1);
+        // The size comes from the file, so huge lists are skipped unread
+        if &chunk[0..4] != b"LIST" || size > MAX_INFO_BYTES {
+            file.seek(SeekFrom::Current(padded)).ok()?;
+            continue;
+        }
+        let mut list = Vec::new();
+        (&mut file)
+            .take(u64::from(size))
+            .read_to_end(&mut list)
+            .ok()?;
+        if list.len() < size as usize {
+            return None;
+        }
+        file.seek(SeekFrom::Current(padded - i64::from(size)))
+            .ok()?;
+        if list.get(0..4) != Some(b"INFO".as_slice()) {
+            continue;
+        }
+        let mut fields = Vec::new();
+        let mut rest = &list[4..];
+        while rest.len() >= 8 {
+            let id: [u8; 4] = rest[0..4].try_into().unwrap();
+            let len = u32::from_le_bytes(rest[4..8].try_into().unwrap()) as usize;
+            let value = rest.get(8..8 + len)?;
+            fields.push((id, value));
+            rest = rest.get(8 + len + (len & 1)..).unwrap_or_default();
+        }
+        let transcript = TRANSCRIPT_INFO_IDS.iter().find_map(|want| {
+            let (_, value) = fields.iter().find(|(id, _)| id == *want)?;
+            let text = String::from_utf8_lossy(value)
+                .trim_end_matches('\0')
+                .trim()
+                .to_string();
+            (!text.is_empty()).then_some(text)
+        });
+        if transcript.is_some() {
+            return transcript;
+        }
+    }
+    None
+}
+
+/// Peak and RMS level of a WAV file, and how many samples sit at full scale.
+#[derive(Debug, Clone, Copy, Serialize)]
+pub struct Levels {
+    /// Largest absolute sample in dBFS; `None` for silence
+    pub peak_dbfs: Option<f64>,
+    /// Root mean square over all samples in dBFS; `None` for silence
+    pub rms_dbfs: Option<f64>,
+    /// Samples at or beyond full scale, a sign of clipping
+    pub clipped_samples: u64,
+    pub samples: u64,
+}
+
+/// Measure the levels of a WAV file over all channels.
+pub fn levels(path: &Path) -> Result<Levels> {
+    let mut reader = hound::WavReader::open(path)
+        .with_context(|| format!("failed to open {}", path.display()))?;
+    let spec = reader.spec();
+    let samples = read_normalized(&mut reader)?;
+    // Positive integer samples stop one step short of 1.0
+    let full_scale = match spec.sample_format {
+        hound::SampleFormat::Float => 1.0,
+        hound::SampleFormat::Int => 1.0 - 1.0 / (1i64 << (spec.bits_per_sample - 1)) as f64,
+    };
+
+    let (mut peak, mut sum_sq, mut clipped) = (0.0f64, 0.0f64, 0u64);
+    for &s in &samples {
+        peak = peak.max(s.abs());
+        sum_sq += s * s;
+        if s.abs() >= full_scale {
+            clipped += 1;
+        }
+    }
+    let dbfs = |level: f64| (level > 0.0).then(|| 20.0 * level.log10());
+    Ok(Levels {
+        peak_dbfs: dbfs(peak),
+        rms_dbfs: dbfs((sum_sq / samples.len().max(1) as f64).sqrt()),
+        clipped_samples: clipped,
+        samples: samples.len() as u64,
+    })
+}
+
+/// Sample rate of a WAV file in Hz.
+pub fn sample_rate(path: &Path) -> Result<u32> {
+    let reader = hound::WavReader::open(path)
+        .with_context(|| format!("failed to open {}", path.display()))?;
+    Ok(reader.spec().sample_rate)
+}
+
+/// Sample format of a WAV file that isn't 16-bit PCM, such as "24-bit PCM"
+/// or "32-bit float". `None` for 16-bit PCM and for files that aren't WAV.
+pub fn non_pcm16_format(path: &Path) -> Option<String> {
+    let spec = hound::WavReader::open(path).ok()?.spec();
+    match spec.sample_format {
+        hound::SampleFormat::Int if spec.bits_per_sample == 16 => None,
+        hound::SampleFormat::Int => Some(format!("{}-bit PCM", spec.bits_per_sample)),
+        hound::SampleFormat::Float => Some(format!("{}-bit float", spec.bits_per_sample)),
+    }
+}
+
+/// Convert a WAV file to 16-bit PCM at the same rate and channel count,
+/// which every backend can read. Float samples beyond full scale are clipped.
+pub fn to_pcm16(src: &Path, dest: &Path) -> Result<()> {
+    let mut reader =
+        hound::WavReader::open(src).with_context(|| format!("failed to open {}", src.display()))?;
+    let spec = hound::WavSpec {
+        bits_per_sample: 16,
+        sample_format: hound::SampleFormat::Int,
+        ..reader.spec()
+    };
+    let samples = read_normalized(&mut reader)?;
+    let mut writer = hound::WavWriter::create(dest, spec)
+        .with_context(|| format!("failed to create {}", dest.display()))?;
+    write_normalized(&mut writer, spec, &samples)?;
+    writer
+        .finalize()
+        .with_context(|| format!("failed to write {}", dest.display()))
+}
+
+/// Format of a WAV file: rate, channels, and sample width and type.
+pub fn spec(path: &Path) -> Result<hound::WavSpec> {
+    let reader = hound::WavReader::open(path)
+        .with_context(|| format!("failed to open {}", path.display()))?;
+    Ok(reader.spec())
+}
+
+/// Whether samples can be written in `spec`'s format.
+pub fn is_writable(spec: hound::WavSpec) -> bool {
+    let width = match spec.sample_format {
+        hound::SampleFormat::Int => matches!(spec.bits_per_sample, 8 | 16 | 24 | 32),
+        hound::SampleFormat::Float => spec.bits_per_sample == 32,
+    };
+    spec.channels > 0 && width
+}
+
+/// Rewrite a WAV file with the channel count and sample format of `like`,
+/// keeping its sample rate. Channels are mixed down, then copied to each
+/// output channel.
+pub fn convert_layout(src: &Path, dest: &Path, like: hound::WavSpec) -> Result<()> {
+    let mut reader =
+        hound::WavReader::open(src).with_context(|| format!("failed to open {}", src.display()))?;
+    let channels = usize::from(reader.spec().channels);
+    let spec = hound::WavSpec {
+        sample_rate: reader.spec().sample_rate,
+        ..like
+    };
+    let samples = read_normalized(&mut reader)?;
+    let mut writer = hound::WavWriter::create(dest, spec)
+        .with_context(|| format!("failed to create {}", dest.display()))?;
+    for frame in samples.chunks(channels) {
+        let mixed = frame.iter().sum::<f64>() / frame.len() as f64;
+        write_normalized(&mut writer, spec, &vec![mixed; usize::from(spec.channels)])?;
+    }
+    writer
+        .finalize()
+        .with_context(|| format!("failed to write {}", dest.display()))
+}
+
+/// Resample a WAV file to `rate` Hz, writing the result to `dest` with the
+/// same channel count and sample format. Uses linear interpolation, with a
+/// moving-average low-pass first when downsampling to limit aliasing.
+pub fn resample(src: &Path, dest: &Path, rate: u32) -> Result<()> {
+    let mut reader =
+        hound::WavReader::open(src).with_context(|| format!("failed to open {}", src.display()))?;
+    let spec = reader.spec();
+    let channels = usize::from(spec.channels);
+    let samples = read_normalized(&mut reader)?;
+
+    let out_spec = hound::WavSpec {
+        sample_rate: rate,
+        ..spec
+    };
+    let mut writer = hound::WavWriter::create(dest, out_spec)
+        .with_context(|| format!("failed to create {}", dest.display()))?;
+
+    let ratio = f64::from(spec.sample_rate) / f64::from(rate);
+    let frames = samples.len() / channels;
+    let out_frames = (frames as f64 / ratio).floor() as usize;
+    let channel_data: Vec<Vec<f64>> = (0..channels)
+        .map(|c| {
+            let data: Vec<f64> = samples.iter().skip(c).step_by(channels).copied().collect();
+            if ratio > 1.0 {
+                moving_average(&data, ratio.ceil() as usize)
+            } else {
+                data
+            }
+        })
+        .collect();
+
+    for i in 0..out_frames {
+        let pos = i as f64 * ratio;
+        let idx = pos.floor() as usize;
+        let frac = pos - idx as f64;
+        for data in &channel_data {
+            let a = data[idx.min(frames - 1)];
+            let b = data[(idx + 1).min(frames - 1)];
+            write_normalized(&mut writer, out_spec, &[a + (b - a) * frac])?;
+        }
+    }
+
+    writer
+        .finalize()
+        .with_context(|| format!("failed to write {}", dest.display()))
+}
+
+/// Centered moving average over `width` samples.
+fn moving_average(data: &[f64], width: usize) -> Vec<f64> {
+    if width <= 1 || data.is_empty() {
+        return data.to_vec();
+    }
+    let half = width / 2;
+    let mut prefix = Vec::with_capacity(data.len() + 1);
+    prefix.push(0.0f64);
+    for &s in data {
+        prefix.push(prefix.last().unwrap() + s);
+    }
+    (0..data.len())
+        .map(|i| {
+            let lo = i.saturating_sub(half);
+            let hi = (i + half + 1).min(data.len());
+            (prefix[hi] - prefix[lo]) / (hi - lo) as f64
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use super::*;
+
+    /// A unique path in the temp directory for one test's files.
+    fn temp_path(name: &str) -> PathBuf {
+        std::env::temp_dir().join(format!("qwen-tts-test-{}-{name}", std::process::id()))
+    }
+
+    /// A RIFF/WAVE file holding `chunks` after a mono 16-bit fmt chunk.
+    fn riff(chunks: &[u8]) -> Vec<u8> {
+        let mut body = b"WAVEfmt ".to_vec();
+        body.extend(16u32.to_le_bytes());
+        for field in [1u16, 1] {
+            body.extend(field.to_le_bytes());
+        }
+        body.extend(24000u32.to_le_bytes());
+        body.extend(48000u32.to_le_bytes());
+        for field in [2u16, 16] {
+            body.extend(field.to_le_bytes());
+        }
+        body.extend(chunks);
+        let mut file = b"RIFF".to_vec();
+        file.extend((body.len() as u32).to_le_bytes());
+        file.extend(body);
+        file
+    }
+
+    fn chunk(id: &[u8], size: u32, data: &[u8]) -> Vec<u8> {
+        let mut chunk = id.to_vec();
+        chunk.extend(size.to_le_bytes());
+        chunk.extend(data);
+        chunk
+    }
+
+    fn write_wav(path: &Path, spec: hound::WavSpec, samples: &[i32]) {
+        let mut writer = hound::WavWriter::create(path, spec).unwrap();
+        for &s in samples {
+            writer.write_sample(s).unwrap();
+        }
+        writer.finalize().unwrap();
+    }
+
+    fn read_wav(path: &Path) -> Vec<i32> {
+        hound::WavReader::open(path)
+            .unwrap()
+            .samples::<i32>()
+            .map(Result::unwrap)
+            .collect()
+    }
+
+    /// Mono PCM at 1 kHz, so one millisecond is one sample.
+    fn pcm(bits: u16) -> hound::WavSpec {
+        hound::WavSpec {
+            channels: 1,
+            sample_rate: 1000,
+            bits_per_sample: bits,
+            sample_format: hound::SampleFormat::Int,
+        }
+    }
+
+    #[test]
+    fn concat_with_gaps() {
+        let (a, b, out) = (
+            temp_path("gap-a.wav"),
+            temp_path("gap-b.wav"),
+            temp_path("gap.wav"),
+        );
+        write_wav(&a, pcm(16), &[1, 2, 3]);
+        write_wav(&b, pcm(16), &[4, 5]);
+        concat_wavs(&[a.clone(), b.clone()], &[2], 0, &out).unwrap();
+        assert_eq!(read_wav(&out), [1, 2, 3, 0, 0, 4, 5]);
+        for path in [a, b, out] {
+            fs::remove_file(path).ok();
+        }
+    }
+
+    #[test]
+    fn crossfade_overlaps_neighbors() {
+        let (a, b, c) = (
+            temp_path("xf-a.wav"),
+            temp_path("xf-b.wav"),
+            temp_path("xf-c.wav"),
+        );
+        let out = temp_path("xf.wav");
+        write_wav(&a, pcm(16), &[1000; 10]);
+        write_wav(&b, pcm(16), &[1000; 4]);
+        write_wav(&c, pcm(16), &[-1000; 10]);
+        let inputs = [a.clone(), b.clone(), c.clone()];
+
+        // Overlaps are capped at half of the shorter input: 2 + 2 samples
+        concat_wavs(&inputs, &[], 5, &out).unwrap();
+        let joined = read_wav(&out);
+        assert_eq!(joined.len(), 24 - 4);
+        // Equal-power fades between equal signals rise slightly above them
+        assert!(joined[..10].iter().all(|&s| (1000..=1415).contains(&s)));
+        assert!(joined[12..].iter().all(|&s| (-1000..=1000).contains(&s)));
+        assert_eq!(joined[19], -1000);
+
+        // No overlap across a gap
+        concat_wavs(&inputs, &[0, 3], 5, &out).unwrap();
+        let joined = read_wav(&out);
+        assert_eq!(joined.len(), 24 - 2 + 3);
+        assert_eq!(joined[12..15], [0, 0, 0]);
+        for path in [a, b, c, out] {
+            fs::remove_file(path).ok();
+        }
+    }
+
+    #[test]
+    fn crossfade_keeps_32_bit_samples_exact() {
+        let (a, b, out) = (
+            temp_path("x32-a.wav"),
+            temp_path("x32-b.wav"),
+            temp_path("x32.wav"),
+        );
+        let loud = [i32::MAX, i32::MIN, 123_456_789, -987_654_321, 1];
+        write_wav(&a, pcm(32), &loud);
+        write_wav(&b, pcm(32), &loud);
+        concat_wavs(&[a.clone(), b.clone()], &[], 1, &out).unwrap();
+        let joined = read_wav(&out);
+        assert_eq!(joined.len(), 9);
+        assert_eq!(joined[..4], loud[..4]);
+        assert_eq!(joined[5..], loud[1..]);
+        for path in [a, b, out] {
+            fs::remove_file(path).ok();
+        }
+    }
+
+    #[test]
+    fn concat_rejects_mixed_formats() {
+        let (a, b, out) = (
+            temp_path("mix-a.wav"),
+            temp_path("mix-b.wav"),
+            temp_path("mix.wav"),
+        );
+        write_wav(&a, pcm(16), &[1]);
+        write_wav(&b, pcm(24), &[1]);
+        assert!(concat_wavs(&[a.clone(), b.clone()], &[], 0, &out).is_err());
+        for path in [a, b, out] {
+            fs::remove_file(path).ok();
+        }
+    }
+
+    #[test]
+    fn convert_layout_remixes_channels_and_width() {
+        let (src, out) = (temp_path("layout-src.wav"), temp_path("layout.wav"));
+        let stereo = hound::WavSpec {
+            channels: 2,
+            ..pcm(16)
+        };
+        write_wav(&src, stereo, &[16384, 0, -16384, -16384]);
+        convert_layout(&src, &out, pcm(24)).unwrap();
+        assert_eq!(spec(&out).unwrap(), pcm(24));
+        assert_eq!(read_wav(&out), [1 << 21, -(1 << 22)]);
+
+        write_wav(&src, pcm(16), &[16384]);
+        let like = hound::WavSpec {
+            sample_rate: 48000,
+            ..stereo
+        };
+        convert_layout(&src, &out, like).unwrap();
+        assert_eq!(spec(&out).unwrap(), stereo);
+        assert_eq!(read_wav(&out), [16384, 16384]);
+        for path in [src, out] {
+            fs::remove_file(path).ok();
+        }
+    }
+
+    #[test]
+    fn transcript_from_info_chunk() {
+        let mut info = b"INFO".to_vec();
+        info.extend(chunk(b"INAM", 4, b"Ryan"));
+        info.extend(chunk(b"ICMT", 6, b"hello\0"));
+        let mut chunks = chunk(b"LIST", info.len() as u32, &info);
+        chunks.extend(chunk(b"data", 4, &[0; 4]));
+        let path = temp_path("info.wav");
+        fs::write(&path, riff(&chunks)).unwrap();
+        assert_eq!(embedded_transcript(&path).as_deref(), Some("hello"));
+        fs::remove_file(&path).ok();
+    }
+
+    #[test]
+    fn oversized_list_chunk_is_not_read() {
+        let info = b"INFOICMT\x05\0\0\0hello\0";
+        let path = temp_path("huge-list.wav");
+        fs::write(&path, riff(&chunk(b"LIST", 0xffff_fff0, info))).unwrap();
+        assert_eq!(embedded_transcript(&path), None);
+        // A list that claims more than the file holds
+        fs::write(&path, riff(&chunk(b"LIST", 1000, info))).unwrap();
+        assert_eq!(embedded_transcript(&path), None);
+        fs::remove_file(&path).ok();
+    }
+}
@@ -0,0 +1,159 @@
+use std::fs;
+use std::path::PathBuf;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+use anyhow::{Context, Result};
+use colored::Colorize;
+use serde::{Deserialize, Serialize};
+
+use crate::audio;
+use crate::config;
+use crate::generate::{self, TtsParams};
+use crate::output;
+
+/// Short warm-up text; its run time approximates process startup + model load.
+const WARMUP_TEXT: &str = "Hello.";
+
+/// Fixed paragraph used for every benchmark so results are comparable.
+const BENCH_TEXT: &str = "The quick brown fox jumps over the lazy dog. \
+    Speech synthesis has come a long way in recent years, and modern models can \
+    produce voices that are expressive, natural, and easy to listen to for long \
+    periods of time. This paragraph is used to measure how quickly the current \
+    backend turns text into audio on this machine.";
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BenchResult {
+    pub backend: String,
+    pub model_variant: String,
+    pub timestamp: u64,
+    pub load_secs: f64,
+    pub generation_secs: f64,
+    pub audio_secs: f64,
+    pub real_time_factor: f64,
+    pub chars_per_sec: f64,
+    pub peak_memory_bytes: Option<u64>,
+}
+
+/// ~/.qwen-tts/bench.toml
+pub fn results_path() -> PathBuf {
+    config::base_dir().join("bench.toml")
+}
+
+fn timed_run(cfg: &config::Config, text: &str, out: &std::path::Path) -> Result<Duration> {
+    let start = Instant::now();
+    generate::run_tts_command(
+        cfg,
+        &TtsParams {
+            text,
+            instruct: &format!("Speak as {}.", cfg.default_voice),
+            speed: 1.0,
+            pitch: 0.0,
+            output_path: out,
+            ref_audio: None,
+            ref_text: None,
+            voice: Some(&cfg.default_voice),
+            join: true,
+        },
+    )
+    .context("benchmark run failed")?;
+    Ok(start.elapsed())
+}
+
+/// Peak resident memory of any finished child process, in bytes.
+#[cfg(unix)]
+fn peak_child_memory() -> Option<u64> {
+    let mut usage = std::mem::MaybeUninit::<libc::rusage>::uninit();
+    // SAFETY: getrusage fills the struct on success and we only read it then.
+    let usage = unsafe {
+        if libc::getrusage(libc::RUSAGE_CHILDREN, usage.as_mut_ptr()) != 0 {
+            return None;
+        }
+        usage.assume_init()
+    };
+    let maxrss = u64::try_from(usage.ru_maxrss).ok()?;
+    // ru_maxrss is reported in bytes on macOS and kilobytes elsewhere
+    if cfg!(target_os = "macos") {
+        Some(maxrss)
+    } else {
+        Some(maxrss * 1024)
+    }
+}
+
+#[cfg(not(unix))]
+fn peak_child_memory() -> Option<u64> {
+    None
+}
+
+pub fn run() -> Result<()> {
+    // A warm backend would hide the model load from the timings and keep
+    // its memory out of the child process counts, so every run starts cold
+    let cfg = config::Config {
+        keep_warm: None,
+        ..config::load()?
+    };
+    let dir = config::base_dir().join("bench");
+    fs::create_dir_all(&dir).with_context(|| format!("failed to create {}", dir.display()))?;
+
+    output::status(
+        "Bench",
+        &format!("backend {} / variant {}", cfg.backend, cfg.model_variant),
+    );
+
+    output::status("Warm-up", "loading model...");
+    let warmup_out = dir.join("warmup");
+    let load = timed_run(&cfg, WARMUP_TEXT, &warmup_out)?;
+
+    output::status("Running", &format!("{} chars...", BENCH_TEXT.len()));
+    let bench_out = dir.join("paragraph");
+    let total = timed_run(&cfg, BENCH_TEXT, &bench_out)?;
+
+    let wav = generate::join_chunks(&bench_out, 0).context("benchmark produced no audio")?;
+    let audio_secs = audio::duration_secs(&wav)?;
+    let generation_secs = total.saturating_sub(load).as_secs_f64();
+
+    let result = BenchResult {
+        backend: cfg.backend.to_string(),
+        model_variant: cfg.model_variant.clone(),
+        timestamp: SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_secs(),
+        load_secs: load.as_secs_f64(),
+        generation_secs,
+        audio_secs,
+        real_time_factor: if audio_secs > 0.0 {
+            generation_secs / audio_secs
+        } else {
+            0.0
+        },
+        chars_per_sec: if generation_secs > 0.0 {
+            BENCH_TEXT.len() as f64 / generation_secs
+        } else {
+            0.0
+        },
+        peak_memory_bytes: peak_child_memory(),
+    };
+
+    fs::remove_dir_all(&dir).ok();
+
+    println!();
+    println!("{}", "Benchmark results".bold());
+    println!("  Backend:          {}", result.backend);
+    println!("  Variant:          {}", result.model_variant);
+    println!("  Model load:       {:.2}s", result.load_secs);
+    println!("  Generation:       {:.2}s", result.generation_secs);
+    println!("  Audio produced:   {:.2}s", result.audio_secs);
+    println!("  Real-time factor: {:.2}", result.real_time_factor);
+    println!("  Throughput:       {:.1} chars/s", result.chars_per_sec);
+    match result.peak_memory_bytes {
+        Some(bytes) => println!("  Peak memory:      {}", crate::models::human_size(bytes)),
+        None => println!("  Peak memory:      (unavailable on this platform)"),
+    }
+
+    let text = toml::to_string_pretty(&result).context("failed to serialize bench result")?;
+    let path = results_path();
+    fs::write(&path, text).with_context(|| format!("failed to write {}", path.display()))?;
+    output::success(&format!("Results saved to {}", path.display()));
+
+    Ok(())
+}
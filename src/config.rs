@@ -1,10 +1,17 @@
 use std::fs;
-use std::path::PathBuf;
+use std::io::IsTerminal;
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
 
 use anyhow::{Context, Result};
 use serde::{Deserialize, Serialize};
 
+use crate::audio;
+use crate::error::ErrorKind;
+use crate::generate;
 use crate::models;
+use crate::normalize::Locale;
+use crate::output;
 use crate::platform::{self, Backend};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -18,10 +25,95 @@ pub struct Config {
     pub default_speed: f32,
     pub auto_play: bool,
     pub model_variant: String,
+    /// Model revision (commit, branch, or tag) to download instead of the latest
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub model_revision: Option<String>,
+    /// URL of a JSON variant → repository map that extends the built-in one
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub models_manifest_url: Option<String>,
     #[serde(default = "default_auto_cleanup")]
     pub auto_cleanup: bool,
     #[serde(default = "default_cleanup_age_hours")]
     pub cleanup_age_hours: u32,
+    #[serde(default = "default_auto_download")]
+    pub auto_download: bool,
+    /// Sample rate enrolled voices are resampled to; 0 keeps the original
+    #[serde(default = "default_voice_sample_rate")]
+    pub voice_sample_rate: u32,
+    /// Output format used when `--format` is absent and the output path has
+    /// no extension, e.g. "mp3"
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub default_format: Option<String>,
+    /// Sample rate outputs are resampled to when `--sample-rate` is absent
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub default_sample_rate: Option<u32>,
+    /// Maximum number of backend processes run at the same time
+    #[serde(default = "default_max_jobs")]
+    pub max_jobs: usize,
+    /// Seconds before a generation run is stopped; unset or 0 means no limit
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub generation_timeout: Option<u64>,
+    /// Seconds the CUDA/CPU backend stays loaded after a generation so the
+    /// next one can reuse it; unset or 0 starts a fresh process every time
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub keep_warm: Option<u64>,
+    /// Hard ceiling on the audio of one backend run, in seconds; longer
+    /// output is cut with a warning
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_output_seconds: Option<f32>,
+    /// Warn when an output's length suggests the backend ignored `--speed`
+    #[serde(default)]
+    pub verify_speed: bool,
+    /// Send the backend's own output to `backend.log` instead of the terminal
+    #[serde(default)]
+    pub quiet_backend: bool,
+    /// Speak numerals, currencies, and dates as English words before
+    /// generation; off by default since the model is multilingual
+    #[serde(default)]
+    pub normalize_numbers: bool,
+    /// Conventions for spoken numbers and dates, e.g. day/month order
+    #[serde(default)]
+    pub number_locale: Locale,
+    /// Template wrapping the `design` description, with a `{description}`
+    /// placeholder; unset sends the description as it is
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub design_prompt: Option<String>,
+    /// Seconds a model download may stall before it is aborted; 0 keeps
+    /// each tool's own default
+    #[serde(default = "default_network_timeout")]
+    pub network_timeout: u64,
+    /// Extra attempts for a failed model download
+    #[serde(default = "default_network_retries")]
+    pub network_retries: u32,
+    /// Proxy URL for downloads, e.g. "http://proxy.example.com:8080";
+    /// defaults to HTTPS_PROXY / HTTP_PROXY from the environment
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub proxy: Option<String>,
+    /// Hugging Face access token for gated model repos; defaults to
+    /// HF_TOKEN / HUGGING_FACE_HUB_TOKEN from the environment
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub hf_token: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub log_file: Option<String>,
+    /// Color theme for messages: default, high-contrast, or mono
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub theme: Option<String>,
+    /// Audio player to use instead of the platform fallback chain
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub player: Option<String>,
+    /// Command line template run by the `custom` backend
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub custom_command: Option<String>,
+    /// Generation script for the CUDA/CPU backends (defaults to
+    /// `<base_dir>/generate_compat.py`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub compat_script: Option<String>,
+    /// Octal permissions for created directories, e.g. "750" (Unix only)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub dir_mode: Option<String>,
+    /// Octal permissions for generated files, e.g. "640" (Unix only)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub file_mode: Option<String>,
 }
 
 fn default_auto_cleanup() -> bool {
@@ -32,6 +124,27 @@ fn default_cleanup_age_hours() -> u32 {
     24
 }
 
+fn default_auto_download() -> bool {
+    true
+}
+
+fn default_max_jobs() -> usize {
+    1
+}
+
+fn default_network_timeout() -> u64 {
+    60
+}
+
+fn default_network_retries() -> u32 {
+    2
+}
+
+/// Native output rate of the Qwen3-TTS speech tokenizer.
+fn default_voice_sample_rate() -> u32 {
+    24_000
+}
+
 impl Default for Config {
     fn default() -> Self {
         let base = base_dir();
@@ -51,38 +164,432 @@ impl Default for Config {
             default_speed: 1.0,
             auto_play: true,
             model_variant: "base".to_string(),
+            model_revision: None,
+            models_manifest_url: None,
             auto_cleanup: true,
             cleanup_age_hours: 24,
+            auto_download: true,
+            voice_sample_rate: default_voice_sample_rate(),
+            default_format: None,
+            default_sample_rate: None,
+            max_jobs: default_max_jobs(),
+            generation_timeout: None,
+            keep_warm: None,
+            max_output_seconds: None,
+            verify_speed: false,
+            quiet_backend: false,
+            normalize_numbers: false,
+            number_locale: Locale::default(),
+            design_prompt: None,
+            network_timeout: default_network_timeout(),
+            network_retries: default_network_retries(),
+            proxy: None,
+            hf_token: None,
+            theme: None,
+            log_file: None,
+            player: None,
+            custom_command: None,
+            compat_script: None,
+            dir_mode: None,
+            file_mode: None,
         }
     }
 }
 
-/// ~/.qwen-tts
+impl Config {
+    /// Proxy for downloads: the `proxy` key, else the standard environment
+    /// variables (HTTPS first, since model hosts are HTTPS).
+    pub fn effective_proxy(&self) -> Option<String> {
+        self.proxy.clone().or_else(|| {
+            ["HTTPS_PROXY", "https_proxy", "HTTP_PROXY", "http_proxy"]
+                .iter()
+                .find_map(|var| std::env::var(var).ok().filter(|v| !v.is_empty()))
+        })
+    }
+
+    /// Hugging Face token: the `hf_token` key, else the environment
+    /// variables huggingface_hub itself reads.
+    pub fn effective_hf_token(&self) -> Option<String> {
+        self.hf_token.clone().or_else(|| {
+            ["HF_TOKEN", "HUGGING_FACE_HUB_TOKEN"]
+                .iter()
+                .find_map(|var| std::env::var(var).ok().filter(|v| !v.is_empty()))
+        })
+    }
+
+    /// Credentials for model downloads. huggingface_hub reads the token
+    /// from the environment; git gets it as an HTTP header scoped to
+    /// huggingface.co through GIT_CONFIG_* variables, so it never lands in
+    /// the command line or the clone's `.git/config`.
+    pub fn hf_auth_env(&self) -> Vec<(String, String)> {
+        let Some(token) = self.effective_hf_token() else {
+            return Vec::new();
+        };
+        // Added after any entries the user already passes to git this way
+        let index: usize = std::env::var("GIT_CONFIG_COUNT")
+            .ok()
+            .and_then(|count| count.trim().parse().ok())
+            .unwrap_or(0);
+        vec![
+            ("HF_TOKEN".to_string(), token.clone()),
+            ("HUGGING_FACE_HUB_TOKEN".to_string(), token.clone()),
+            ("GIT_CONFIG_COUNT".to_string(), (index + 1).to_string()),
+            (
+                format!("GIT_CONFIG_KEY_{index}"),
+                "http.https://huggingface.co/.extraHeader".to_string(),
+            ),
+            (
+                format!("GIT_CONFIG_VALUE_{index}"),
+                format!("Authorization: Bearer {token}"),
+            ),
+        ]
+    }
+
+    /// Environment for networked subprocesses (huggingface_hub, git,
+    /// yt-dlp), so every download follows the same proxy and timeout
+    /// settings.
+    pub fn network_env(&self) -> Vec<(&'static str, String)> {
+        let mut env = Vec::new();
+        // Tools disagree on which spelling they read (curl ignores
+        // HTTP_PROXY, requests reads either), so set all of them
+        if let Some(proxy) = self.effective_proxy() {
+            for var in ["HTTPS_PROXY", "https_proxy", "HTTP_PROXY", "http_proxy"] {
+                env.push((var, proxy.clone()));
+            }
+        }
+        if self.network_timeout > 0 {
+            let secs = self.network_timeout.to_string();
+            env.push(("HF_HUB_DOWNLOAD_TIMEOUT", secs.clone()));
+            env.push(("HF_HUB_ETAG_TIMEOUT", secs.clone()));
+            // git gives up when a transfer stays below 1 KB/s for this long
+            env.push(("GIT_HTTP_LOW_SPEED_LIMIT", "1000".to_string()));
+            env.push(("GIT_HTTP_LOW_SPEED_TIME", secs));
+        }
+        env
+    }
+}
+
+/// `QWEN_TTS_HOME`, or ~/.qwen-tts
 pub fn base_dir() -> PathBuf {
+    if let Some(home) = std::env::var_os("QWEN_TTS_HOME").filter(|p| !p.is_empty()) {
+        return expand_path(&home.to_string_lossy());
+    }
     dirs::home_dir()
         .expect("could not determine home directory")
         .join(".qwen-tts")
 }
 
-/// ~/.config/qwen-tts/config.toml
+/// Details of the most recent failed command, shown by `qwen-tts logs last`.
+pub fn last_error_path() -> PathBuf {
+    base_dir().join("last_error.log")
+}
+
+/// Output of the backend runs of the latest command with `quiet_backend`,
+/// shown by `qwen-tts logs backend`.
+pub fn backend_log_path() -> PathBuf {
+    base_dir().join("backend.log")
+}
+
+/// Editor text kept after a cancelled session, offered again on the next open.
+pub fn draft_path() -> PathBuf {
+    base_dir().join("draft.txt")
+}
+
+/// PyTorch generation script for the CUDA/CPU backends, embedded at build time.
+const COMPAT_SCRIPT: &str = include_str!("../scripts/generate_compat.py");
+
+/// Write the embedded `generate_compat.py` into the base directory if it is
+/// missing or was installed by a different qwen-tts version.
+pub fn install_compat_script() -> Result<()> {
+    let dir = base_dir();
+    let script = dir.join("generate_compat.py");
+    let stamp = dir.join(".generate_compat.version");
+    let version = env!("CARGO_PKG_VERSION");
+
+    let current = script.is_file()
+        && fs::read_to_string(&stamp)
+            .map(|v| v.trim() == version)
+            .unwrap_or(false);
+    if current {
+        return Ok(());
+    }
+
+    fs::create_dir_all(&dir).with_context(|| format!("failed to create {}", dir.display()))?;
+    fs::write(&script, COMPAT_SCRIPT)
+        .with_context(|| format!("failed to write {}", script.display()))?;
+    fs::write(&stamp, version).with_context(|| format!("failed to write {}", stamp.display()))?;
+    Ok(())
+}
+
+impl Config {
+    /// Script run for the CUDA/CPU backends.
+    pub fn compat_script_path(&self) -> PathBuf {
+        match &self.compat_script {
+            Some(p) => expand_path(p),
+            None => base_dir().join("generate_compat.py"),
+        }
+    }
+}
+
+static CONFIG_OVERRIDE: OnceLock<PathBuf> = OnceLock::new();
+
+/// Use `path` instead of the default config location for this process.
+/// Set from the global `--config` flag before any config is loaded.
+pub fn set_config_override(path: &str) {
+    let _ = CONFIG_OVERRIDE.set(expand_path(path));
+}
+
+/// `--config` flag, then `QWEN_TTS_CONFIG`, then ~/.config/qwen-tts/config.toml
 pub fn config_path() -> PathBuf {
+    if let Some(path) = CONFIG_OVERRIDE.get() {
+        return path.clone();
+    }
+    if let Some(path) = std::env::var_os("QWEN_TTS_CONFIG").filter(|p| !p.is_empty()) {
+        return expand_path(&path.to_string_lossy());
+    }
     dirs::config_dir()
         .expect("could not determine config directory")
         .join("qwen-tts")
         .join("config.toml")
 }
 
+static BACKEND_OVERRIDE: OnceLock<Backend> = OnceLock::new();
+static NO_DOWNLOAD: OnceLock<()> = OnceLock::new();
+static QUIET_BACKEND: OnceLock<()> = OnceLock::new();
+static TIMEOUT_OVERRIDE: OnceLock<u64> = OnceLock::new();
+static JOBS_OVERRIDE: OnceLock<usize> = OnceLock::new();
+static MODELS_DIR_OVERRIDE: OnceLock<String> = OnceLock::new();
+static OUTPUT_DIR_OVERRIDE: OnceLock<String> = OnceLock::new();
+
+/// Use `backend` for this process regardless of the configured value.
+/// Set from the global `--backend` flag; never written back to the config file.
+pub fn set_backend_override(backend: Backend) {
+    let _ = BACKEND_OVERRIDE.set(backend);
+}
+
+/// Disable model downloads for this process (the global `--no-download` flag).
+pub fn set_no_download() {
+    let _ = NO_DOWNLOAD.set(());
+}
+
+/// Keep backend output off the terminal for this process (the global
+/// `--quiet-backend` flag).
+pub fn set_quiet_backend() {
+    let _ = QUIET_BACKEND.set(());
+}
+
+/// Use `secs` as the generation timeout for this process (the global
+/// `--timeout` flag); 0 disables it.
+pub fn set_timeout_override(secs: u64) {
+    let _ = TIMEOUT_OVERRIDE.set(secs);
+}
+
+/// Run up to `jobs` backend processes at once for this process (the global
+/// `--jobs` flag).
+pub fn set_jobs_override(jobs: usize) {
+    let _ = JOBS_OVERRIDE.set(jobs);
+}
+
+/// Use `path` as the models directory for this process (the global
+/// `--models-dir` flag).
+pub fn set_models_dir_override(path: &str) {
+    let _ = MODELS_DIR_OVERRIDE.set(expand_path(path).to_string_lossy().to_string());
+}
+
+/// Use `path` as the output directory for this process (the global
+/// `--output-dir` flag).
+pub fn set_output_dir_override(path: &str) {
+    let _ = OUTPUT_DIR_OVERRIDE.set(expand_path(path).to_string_lossy().to_string());
+}
+
+/// Whether the global `--output-dir` flag was given.
+pub fn output_dir_overridden() -> bool {
+    OUTPUT_DIR_OVERRIDE.get().is_some()
+}
+
+fn apply_overrides(mut cfg: Config) -> Config {
+    if let Some(backend) = BACKEND_OVERRIDE.get() {
+        cfg.backend = *backend;
+    }
+    if NO_DOWNLOAD.get().is_some() {
+        cfg.auto_download = false;
+    }
+    if QUIET_BACKEND.get().is_some() {
+        cfg.quiet_backend = true;
+    }
+    if let Some(secs) = TIMEOUT_OVERRIDE.get() {
+        cfg.generation_timeout = Some(*secs);
+    }
+    if let Some(jobs) = JOBS_OVERRIDE.get() {
+        cfg.max_jobs = *jobs;
+    }
+    if let Some(dir) = MODELS_DIR_OVERRIDE.get() {
+        cfg.models_dir = dir.clone();
+    }
+    if let Some(dir) = OUTPUT_DIR_OVERRIDE.get() {
+        cfg.output_dir = dir.clone();
+    }
+    cfg
+}
+
+/// Per-project config file, layered over the global config.
+pub const PROJECT_CONFIG: &str = ".qwen-tts.toml";
+
+/// Keys whose relative values in a project config are resolved against the
+/// directory containing it.
+const PROJECT_PATH_KEYS: &[&str] = &[
+    "python_path",
+    "models_dir",
+    "voices_dir",
+    "output_dir",
+    "log_file",
+    "compat_script",
+];
+
+/// Nearest `.qwen-tts.toml` in the current directory or one of its parents.
+pub fn project_config_path() -> Option<PathBuf> {
+    let cwd = std::env::current_dir().ok()?;
+    cwd.ancestors()
+        .map(|dir| dir.join(PROJECT_CONFIG))
+        .find(|path| path.is_file())
+}
+
+/// Layer the keys set in the nearest project config over `cfg`.
+fn apply_project(cfg: Config) -> Result<Config> {
+    let Some(path) = project_config_path() else {
+        return Ok(cfg);
+    };
+    let text =
+        fs::read_to_string(&path).with_context(|| format!("failed to read {}", path.display()))?;
+    let mut project: toml::Table =
+        toml::from_str(&text).with_context(|| format!("failed to parse {}", path.display()))?;
+
+    let dir = path.parent().unwrap_or(Path::new("."));
+    for key in PROJECT_PATH_KEYS {
+        if let Some(toml::Value::String(value)) = project.get_mut(*key) {
+            if !value.starts_with('~') && Path::new(value.as_str()).is_relative() {
+                *value = dir.join(&*value).to_string_lossy().to_string();
+            }
+        }
+    }
+
+    let mut merged = toml::Table::try_from(&cfg).context("failed to serialize config")?;
+    merged.extend(project);
+    merged
+        .try_into()
+        .with_context(|| format!("invalid value in {}", path.display()))
+}
+
+/// Resolve relative directory settings of the global config against
+/// `base_dir()`, so they mean the same wherever the command runs. The file
+/// keeps them as written.
+fn resolve_dirs(mut cfg: Config) -> Config {
+    for dir in [
+        &mut cfg.models_dir,
+        &mut cfg.voices_dir,
+        &mut cfg.output_dir,
+    ] {
+        *dir = expand_dir(dir).to_string_lossy().to_string();
+    }
+    cfg
+}
+
+/// Load the effective config: the global config file, then the nearest
+/// project config, then per-invocation overrides.
 pub fn load() -> Result<Config> {
+    let cfg = load_file()
+        .map(resolve_dirs)
+        .and_then(apply_project)
+        .map(apply_overrides)?;
+    check_dirs(&cfg)?;
+    Ok(cfg)
+}
+
+/// Directory settings, by key.
+fn dir_settings(cfg: &Config) -> [(&'static str, &str); 3] {
+    [
+        ("models_dir", &cfg.models_dir),
+        ("voices_dir", &cfg.voices_dir),
+        ("output_dir", &cfg.output_dir),
+    ]
+}
+
+/// Fail early when a directory setting names an existing regular file,
+/// which would otherwise surface later as an obscure create_dir error.
+fn check_dirs(cfg: &Config) -> Result<()> {
+    for (key, dir) in dir_settings(cfg) {
+        let overridden = match key {
+            "models_dir" => MODELS_DIR_OVERRIDE.get().is_some(),
+            "output_dir" => OUTPUT_DIR_OVERRIDE.get().is_some(),
+            _ => false,
+        };
+        let path = expand_path(dir);
+        if overridden && path.is_file() {
+            return Err(ErrorKind::Config.error(format!(
+                "--{} {} is a file, not a directory",
+                key.replace('_', "-"),
+                path.display()
+            )));
+        }
+        match project_setting(key) {
+            Some(project) if path.is_file() => {
+                return Err(ErrorKind::Config.error(format!(
+                    "{key} is {}, which is a file, not a directory\n\
+                     Point it at a directory in {}, or move the file out of the way",
+                    path.display(),
+                    project.display()
+                )));
+            }
+            _ => check_not_file(key, &path)?,
+        }
+    }
+    Ok(())
+}
+
+/// The project config, if it sets `key`.
+fn project_setting(key: &str) -> Option<PathBuf> {
+    let path = project_config_path()?;
+    let table: toml::Table = toml::from_str(&fs::read_to_string(&path).ok()?).ok()?;
+    table.contains_key(key).then_some(path)
+}
+
+fn check_not_file(key: &str, path: &Path) -> Result<()> {
+    if !path.is_file() {
+        return Ok(());
+    }
+    Err(ErrorKind::Config.error(format!(
+        "{key} is {}, which is a file, not a directory\n\
+         Point it at a directory with `qwen-tts config set {key} <path>` (config: {}), \
+         or move the file out of the way",
+        path.display(),
+        config_path().display()
+    )))
+}
+
+/// Load the config file as stored on disk, creating it on first use.
+fn load_file() -> Result<Config> {
     let path = config_path();
     if !path.exists() {
         // Auto-initialize on first use
-        let cfg = Config::default();
+        let mut cfg = Config::default();
         ensure_dirs(&cfg)?;
         save(&cfg)?;
+        install_compat_script()?;
         eprintln!("First run — config created at {}", path.display());
         eprintln!("Platform: {}", platform::platform_summary());
+        if let Some(note) = platform::detection_note() {
+            output::status("Note", &note);
+        }
         eprintln!();
-        models::auto_download_if_needed(&cfg);
+        if let Some(variant) = models::auto_download_if_needed(&apply_overrides(cfg.clone())) {
+            cfg.model_variant = variant;
+            save(&cfg)?;
+            eprintln!(
+                "Saved model_variant = \"{}\" to the config.",
+                cfg.model_variant
+            );
+            eprintln!();
+        }
         return Ok(cfg);
     }
     let text =
@@ -92,17 +599,120 @@ pub fn load() -> Result<Config> {
     Ok(cfg)
 }
 
+/// Read the config file if it exists, without first-run initialization.
+pub fn read_existing() -> Option<Config> {
+    let text = fs::read_to_string(config_path()).ok()?;
+    toml::from_str(&text).ok().map(resolve_dirs)
+}
+
+/// `read_existing` with the nearest project config layered over it. A
+/// project config that can't be read is ignored here; `load` reports it.
+pub fn read_existing_with_project() -> Option<Config> {
+    let cfg = read_existing()?;
+    Some(apply_project(cfg.clone()).unwrap_or(cfg))
+}
+
 pub fn load_or_default() -> Config {
-    load().unwrap_or_default()
+    load().unwrap_or_else(|_| apply_overrides(Config::default()))
 }
 
 fn ensure_dirs(cfg: &Config) -> Result<()> {
-    for dir in [&cfg.models_dir, &cfg.voices_dir, &cfg.output_dir] {
-        fs::create_dir_all(dir).with_context(|| format!("failed to create directory {dir}"))?;
+    for (key, dir) in dir_settings(cfg) {
+        check_not_file(key, &expand_path(dir))?;
+        fs::create_dir_all(expand_path(dir)).with_context(|| {
+            format!(
+                "failed to create directory {dir} (configured in {})",
+                config_path().display()
+            )
+        })?;
+        apply_dir_mode(cfg, &expand_path(dir))?;
     }
     Ok(())
 }
 
+/// Create `dir` if needed and verify files can be written into it, so a
+/// read-only location fails up front with a pointer to the `key` setting.
+pub fn ensure_writable(dir: &Path, key: &str) -> Result<()> {
+    let hint = format!(
+        "choose another location with `qwen-tts config set {key} <path>` (config: {})",
+        config_path().display()
+    );
+    ensure_writable_with_hint(dir, &hint)
+}
+
+/// `ensure_writable` for directories that don't come from a config key;
+/// `hint` says how to pick another one.
+pub fn ensure_writable_with_hint(dir: &Path, hint: &str) -> Result<()> {
+    if dir.is_file() {
+        return Err(ErrorKind::Io.error(format!(
+            "{} is a file, not a directory\n{hint}",
+            dir.display()
+        )));
+    }
+    fs::create_dir_all(dir).map_err(|e| {
+        ErrorKind::Io.error(format!("cannot create {}: {e}\n{hint}", dir.display()))
+    })?;
+
+    let probe = dir.join(format!(".qwen-tts-write-test-{}", std::process::id()));
+    match fs::write(&probe, b"") {
+        Ok(()) => {
+            fs::remove_file(&probe).ok();
+            Ok(())
+        }
+        Err(e) => {
+            Err(ErrorKind::Io.error(format!("{} is not writable: {e}\n{hint}", dir.display())))
+        }
+    }
+}
+
+/// Parse an octal permission string such as "750" or "0640".
+pub fn parse_mode(value: &str) -> Result<u32> {
+    let mode = u32::from_str_radix(value.trim_start_matches("0o"), 8)
+        .map_err(|_| ErrorKind::Config.error(format!("invalid octal mode: {value}")))?;
+    if mode > 0o7777 {
+        return Err(ErrorKind::Config.error(format!("invalid octal mode: {value}")));
+    }
+    Ok(mode)
+}
+
+#[cfg(unix)]
+fn set_mode(path: &Path, mode: Option<&str>) -> Result<()> {
+    use std::os::unix::fs::PermissionsExt;
+
+    if let Some(mode) = mode {
+        let mode = parse_mode(mode)?;
+        fs::set_permissions(path, fs::Permissions::from_mode(mode))
+            .with_context(|| format!("failed to set permissions on {}", path.display()))?;
+    }
+    Ok(())
+}
+
+#[cfg(not(unix))]
+fn set_mode(_path: &Path, _mode: Option<&str>) -> Result<()> {
+    Ok(())
+}
+
+/// Apply the configured `dir_mode` to a directory. No-op on Windows.
+pub fn apply_dir_mode(cfg: &Config, path: &Path) -> Result<()> {
+    set_mode(path, cfg.dir_mode.as_deref())
+}
+
+/// Apply the configured `file_mode` to a file, or to a directory and the files
+/// directly inside it. No-op on Windows.
+pub fn apply_output_mode(cfg: &Config, path: &Path) -> Result<()> {
+    if path.is_dir() {
+        apply_dir_mode(cfg, path)?;
+        for entry in fs::read_dir(path)?.flatten() {
+            if entry.path().is_file() {
+                set_mode(&entry.path(), cfg.file_mode.as_deref())?;
+            }
+        }
+        Ok(())
+    } else {
+        set_mode(path, cfg.file_mode.as_deref())
+    }
+}
+
 pub fn save(cfg: &Config) -> Result<()> {
     let path = config_path();
     if let Some(parent) = path.parent() {
@@ -114,37 +724,137 @@ pub fn save(cfg: &Config) -> Result<()> {
     Ok(())
 }
 
-pub fn init() -> Result<()> {
-    let cfg = Config::default();
+/// Write a default config. An existing config is only replaced with
+/// `force` or after confirming at the prompt; with `keep` nothing is asked
+/// and an existing config is kept, so scripted setup can run repeatedly.
+pub fn init(force: bool, keep: bool) -> Result<()> {
+    let path = config_path();
+    if path.exists() && !force {
+        if keep {
+            let cfg = resolve_dirs(load_file()?);
+            ensure_dirs(&cfg)?;
+            install_compat_script()?;
+            println!("Config already exists at {}; kept it", path.display());
+            return Ok(());
+        }
+        let interactive = std::io::stdin().is_terminal() && std::io::stderr().is_terminal();
+        if !interactive {
+            return Err(ErrorKind::Config.error(format!(
+                "config already exists at {}\n\
+                 Pass --force to overwrite it with defaults, or --keep to keep it",
+                path.display()
+            )));
+        }
+        output::warn(&format!(
+            "config already exists at {}; your settings will be replaced",
+            path.display()
+        ));
+        if !output::prompt_yn("Overwrite it with defaults?", false) {
+            println!("Kept the existing config.");
+            return Ok(());
+        }
+    }
+
+    let mut cfg = Config::default();
+    cfg.model_variant = models::suggested_variant(&cfg);
     ensure_dirs(&cfg)?;
     save(&cfg)?;
-    println!("Config initialized at {}", config_path().display());
+    install_compat_script()?;
+    println!("Config initialized at {}", path.display());
     println!("Platform: {}", platform::platform_summary());
     println!("Backend:  {}", cfg.backend);
+    if let Some(note) = platform::detection_note() {
+        output::status("Note", &note);
+    }
+    println!("Variant:  {}", cfg.model_variant);
     Ok(())
 }
 
-pub fn show() -> Result<()> {
-    let cfg = load()?;
+pub fn show(json: bool) -> Result<()> {
+    let mut cfg = load()?;
+    cfg.hf_token = cfg.hf_token.as_deref().map(redact_token);
+    cfg.proxy = cfg.proxy.as_deref().map(redact_proxy);
+    if json {
+        return output::print_json(&cfg);
+    }
     let text = toml::to_string_pretty(&cfg).context("failed to serialize config")?;
     println!("{}", text);
+    // Derived values, printed as comments so the output stays valid TOML
+    match models::repo_id(cfg.backend, &cfg.model_variant) {
+        Ok(repo) => println!("# model repo: {repo}"),
+        Err(_) => println!("# model repo: (unknown variant)"),
+    }
+    println!(
+        "# model status: {}",
+        models::install_state(&cfg, &cfg.model_variant)
+    );
+    if let Some(project) = project_config_path() {
+        println!("# project config: {}", project.display());
+    }
     Ok(())
 }
 
+/// Display form of a config key's current value, for `config set` output.
+fn display_value(cfg: &Config, key: &str) -> String {
+    toml::Table::try_from(cfg)
+        .ok()
+        .and_then(|table| table.get(key).cloned())
+        .map(|value| match value {
+            toml::Value::String(s) if key == "hf_token" => redact_token(&s),
+            toml::Value::String(s) if key == "proxy" => redact_proxy(&s),
+            toml::Value::String(s) => s,
+            other => other.to_string(),
+        })
+        .unwrap_or_else(|| "(unset)".to_string())
+}
+
 pub fn set(key: &str, value: &str) -> Result<()> {
-    let mut cfg = load()?;
+    let mut cfg = load_file()?;
+    let old = display_value(&cfg, key);
+
+    apply_setting(&mut cfg, key, value)
+        .with_context(|| format!("cannot set {key} in {}", config_path().display()))?;
 
+    save(&cfg)?;
+    let new = display_value(&cfg, key);
+    if old == new {
+        println!("{key}: {new} (unchanged)");
+    } else {
+        println!("{key}: {old} → {new}");
+    }
+    Ok(())
+}
+
+/// Validate `value` and store it under `key`.
+fn apply_setting(cfg: &mut Config, key: &str, value: &str) -> Result<()> {
     match key {
         "python_path" => cfg.python_path = value.to_string(),
-        "models_dir" => cfg.models_dir = value.to_string(),
-        "voices_dir" => cfg.voices_dir = value.to_string(),
-        "output_dir" => cfg.output_dir = value.to_string(),
+        "models_dir" => {
+            check_not_file(key, &expand_dir(value))?;
+            cfg.models_dir = value.to_string();
+        }
+        "voices_dir" => {
+            check_not_file(key, &expand_dir(value))?;
+            cfg.voices_dir = value.to_string();
+        }
+        "output_dir" => {
+            check_not_file(key, &expand_dir(value))?;
+            cfg.output_dir = value.to_string();
+        }
         "backend" => cfg.backend = value.parse()?,
         "default_voice" => cfg.default_voice = value.to_string(),
         "default_speed" => {
-            cfg.default_speed = value
+            let speed: f32 = value
                 .parse()
                 .with_context(|| format!("invalid speed: {value}"))?;
+            if !generate::SPEED_RANGE.contains(&speed) {
+                return Err(ErrorKind::Config.error(format!(
+                    "default_speed must be between {} and {}",
+                    generate::SPEED_RANGE.start(),
+                    generate::SPEED_RANGE.end()
+                )));
+            }
+            cfg.default_speed = speed;
         }
         "auto_play" => {
             cfg.auto_play = value
@@ -152,19 +862,71 @@ pub fn set(key: &str, value: &str) -> Result<()> {
                 .with_context(|| format!("invalid bool: {value}"))?;
         }
         "model_variant" => {
-            let valid = [
-                "base",
-                "base-4bit",
-                "custom",
-                "custom-4bit",
-                "design",
-                "design-4bit",
-            ];
-            if !valid.contains(&value) {
-                anyhow::bail!("model_variant must be one of: {}", valid.join(", "));
+            if !models::variants().contains(&value) {
+                return Err(ErrorKind::Config.error(format!(
+                    "model_variant must be one of: {}",
+                    models::variants().join(", ")
+                )));
             }
             cfg.model_variant = value.to_string();
         }
+        "model_revision" => {
+            cfg.model_revision = if value.is_empty() {
+                None
+            } else {
+                Some(value.to_string())
+            };
+        }
+        "models_manifest_url" => {
+            cfg.models_manifest_url = if value.is_empty() {
+                None
+            } else {
+                Some(value.to_string())
+            };
+        }
+        "max_output_seconds" => {
+            cfg.max_output_seconds = if value.is_empty() || value == "0" {
+                None
+            } else {
+                let secs: f32 = value
+                    .parse()
+                    .with_context(|| format!("invalid number of seconds: {value}"))?;
+                if !(secs > 0.0 && secs.is_finite()) {
+                    return Err(ErrorKind::Config.error(format!(
+                        "max_output_seconds must be a positive number, got {value}"
+                    )));
+                }
+                Some(secs)
+            };
+        }
+        "normalize_numbers" => {
+            cfg.normalize_numbers = value
+                .parse()
+                .with_context(|| format!("invalid bool: {value}"))?;
+        }
+        "number_locale" => cfg.number_locale = value.parse()?,
+        "design_prompt" => {
+            cfg.design_prompt = if value.is_empty() {
+                None
+            } else if !value.contains("{description}") {
+                return Err(ErrorKind::Config.error(format!(
+                    "design_prompt must contain {{description}} where the voice description goes, \
+                     e.g. \"Voice: {{description}}\" (got: {value})"
+                )));
+            } else {
+                Some(value.to_string())
+            };
+        }
+        "verify_speed" => {
+            cfg.verify_speed = value
+                .parse()
+                .with_context(|| format!("invalid bool: {value}"))?;
+        }
+        "quiet_backend" => {
+            cfg.quiet_backend = value
+                .parse()
+                .with_context(|| format!("invalid bool: {value}"))?;
+        }
         "auto_cleanup" => {
             cfg.auto_cleanup = value
                 .parse()
@@ -175,14 +937,244 @@ pub fn set(key: &str, value: &str) -> Result<()> {
                 .parse()
                 .with_context(|| format!("invalid u32: {value}"))?;
         }
-        _ => anyhow::bail!("unknown config key: {key}"),
+        "auto_download" => {
+            cfg.auto_download = value
+                .parse()
+                .with_context(|| format!("invalid bool: {value}"))?;
+        }
+        "voice_sample_rate" => {
+            let rate: u32 = value
+                .parse()
+                .with_context(|| format!("invalid sample rate: {value}"))?;
+            // 0 keeps each reference at its original rate
+            if rate != 0 {
+                audio::validate_sample_rate(rate)?;
+            }
+            cfg.voice_sample_rate = rate;
+        }
+        "default_format" => {
+            cfg.default_format = if value.is_empty() {
+                None
+            } else {
+                let format = value.trim_start_matches('.').to_lowercase();
+                audio::validate_format(&format)?;
+                Some(format)
+            };
+        }
+        "default_sample_rate" => {
+            cfg.default_sample_rate = if value.is_empty() || value == "0" {
+                None
+            } else {
+                let rate: u32 = value
+                    .parse()
+                    .with_context(|| format!("invalid sample rate: {value}"))?;
+                audio::validate_sample_rate(rate)?;
+                Some(rate)
+            };
+        }
+        "max_jobs" => {
+            let jobs: usize = value
+                .parse()
+                .with_context(|| format!("invalid number of jobs: {value}"))?;
+            if jobs == 0 {
+                return Err(ErrorKind::Config.error("max_jobs must be at least 1"));
+            }
+            cfg.max_jobs = jobs;
+        }
+        "generation_timeout" => {
+            cfg.generation_timeout = if value.is_empty() || value == "0" {
+                None
+            } else {
+                Some(
+                    value
+                        .parse()
+                        .with_context(|| format!("invalid number of seconds: {value}"))?,
+                )
+            };
+        }
+        "keep_warm" => {
+            cfg.keep_warm = if value.is_empty() || value == "0" {
+                None
+            } else {
+                Some(
+                    value
+                        .parse()
+                        .with_context(|| format!("invalid number of seconds: {value}"))?,
+                )
+            };
+        }
+        "network_timeout" => {
+            cfg.network_timeout = value
+                .parse()
+                .with_context(|| format!("invalid number of seconds: {value}"))?;
+        }
+        "network_retries" => {
+            cfg.network_retries = value
+                .parse()
+                .with_context(|| format!("invalid number of retries: {value}"))?;
+        }
+        "proxy" => {
+            cfg.proxy = if value.is_empty() {
+                None
+            } else {
+                validate_proxy(value)?;
+                Some(value.to_string())
+            };
+        }
+        "hf_token" => {
+            cfg.hf_token = if value.is_empty() {
+                None
+            } else if value.chars().any(char::is_whitespace) {
+                return Err(ErrorKind::Config.error("hf_token must not contain whitespace"));
+            } else {
+                Some(value.to_string())
+            };
+        }
+        "theme" => {
+            cfg.theme = if value.is_empty() {
+                None
+            } else {
+                value.parse::<output::Theme>()?;
+                Some(value.to_lowercase())
+            };
+        }
+        "log_file" => {
+            cfg.log_file = if value.is_empty() {
+                None
+            } else {
+                Some(value.to_string())
+            };
+        }
+        "player" => {
+            cfg.player = if value.is_empty() {
+                None
+            } else {
+                Some(value.to_string())
+            };
+        }
+        "custom_command" => {
+            cfg.custom_command = if value.is_empty() {
+                None
+            } else {
+                platform::split_command(value)?;
+                Some(value.to_string())
+            };
+        }
+        "compat_script" => {
+            cfg.compat_script = if value.is_empty() {
+                None
+            } else {
+                let path = expand_path(value);
+                if !path.is_file() {
+                    return Err(ErrorKind::Config
+                        .error(format!("compat script not found: {}", path.display())));
+                }
+                Some(value.to_string())
+            };
+        }
+        "dir_mode" | "file_mode" => {
+            let mode = if value.is_empty() {
+                None
+            } else {
+                parse_mode(value)?;
+                Some(value.to_string())
+            };
+            if key == "dir_mode" {
+                cfg.dir_mode = mode;
+            } else {
+                cfg.file_mode = mode;
+            }
+        }
+        _ => return Err(ErrorKind::Config.error(format!("unknown config key: {key}"))),
+    }
+    Ok(())
+}
+
+/// Proxy URL schemes understood by huggingface_hub, git, and yt-dlp.
+const PROXY_SCHEMES: &[&str] = &["http", "https", "socks5", "socks5h", "socks4"];
+
+fn validate_proxy(value: &str) -> Result<()> {
+    match value.split_once("://") {
+        Some((scheme, host)) if PROXY_SCHEMES.contains(&scheme) && !host.is_empty() => Ok(()),
+        _ => Err(ErrorKind::Config.error(format!(
+            "invalid proxy URL: {value} (expected e.g. http://proxy.example.com:8080; \
+             schemes: {})",
+            PROXY_SCHEMES.join(", ")
+        ))),
     }
+}
 
-    save(&cfg)?;
-    println!("Set {key} = {value}");
+/// Proxy URL with any `user:password@` credentials removed, for display.
+pub fn redact_proxy(proxy: &str) -> String {
+    match proxy.split_once("://") {
+        Some((scheme, rest)) => format!("{scheme}://{}", rest.rsplit('@').next().unwrap_or(rest)),
+        None => proxy.rsplit('@').next().unwrap_or(proxy).to_string(),
+    }
+}
+
+/// Access token shortened to its last four characters, for display.
+pub fn redact_token(token: &str) -> String {
+    let chars: Vec<char> = token.chars().collect();
+    if chars.len() <= 8 {
+        return "****".to_string();
+    }
+    let tail: String = chars[chars.len() - 4..].iter().collect();
+    format!("****{tail}")
+}
+
+/// Print the resolved location of a data directory (or all of them), and
+/// optionally reveal it in the file manager.
+pub fn show_path(kind: Option<&str>, open: bool) -> Result<()> {
+    let cfg = load_or_default();
+    let config_dir = config_path()
+        .parent()
+        .map(Path::to_path_buf)
+        .unwrap_or_default();
+    let paths = [
+        ("config", config_dir),
+        ("base", base_dir()),
+        ("models", expand_path(&cfg.models_dir)),
+        ("voices", expand_path(&cfg.voices_dir)),
+        ("output", expand_path(&cfg.output_dir)),
+    ];
+
+    let Some(kind) = kind else {
+        for (name, path) in &paths {
+            println!("{name:<8}{}", path.display());
+        }
+        return Ok(());
+    };
+
+    let path = paths
+        .iter()
+        .find(|(name, _)| *name == kind)
+        .map(|(_, path)| path)
+        .with_context(|| {
+            let names: Vec<_> = paths.iter().map(|(name, _)| *name).collect();
+            format!(
+                "unknown path: {kind} (expected one of: {})",
+                names.join(", ")
+            )
+        })?;
+
+    println!("{}", path.display());
+    if open {
+        platform::open_in_file_manager(path)?;
+    }
     Ok(())
 }
 
+/// Expand ~ like [`expand_path`], and resolve a relative directory setting
+/// against `base_dir()` rather than the current directory.
+fn expand_dir(p: &str) -> PathBuf {
+    let path = expand_path(p);
+    if path.is_relative() {
+        base_dir().join(path)
+    } else {
+        path
+    }
+}
+
 /// Expand ~ to home directory in a path string.
 pub fn expand_path(p: &str) -> PathBuf {
     if let Some(rest) = p.strip_prefix("~/") {
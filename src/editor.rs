@@ -1,5 +1,7 @@
 use anyhow::{Context, Result};
-use crossterm::event::{self, Event, KeyCode, KeyEvent, KeyModifiers};
+use crossterm::event::{
+    self, DisableBracketedPaste, EnableBracketedPaste, Event, KeyCode, KeyEvent, KeyModifiers,
+};
 use crossterm::terminal::{self, EnterAlternateScreen, LeaveAlternateScreen};
 use crossterm::ExecutableCommand;
 use ratatui::layout::{Constraint, Layout};
@@ -7,37 +9,165 @@ use ratatui::style::{Color, Modifier, Style};
 use ratatui::text::{Line, Span};
 use ratatui::widgets::{Block, Borders, Paragraph};
 use ratatui::Terminal;
-use std::io;
+use std::fs;
+use std::io::{self, IsTerminal, Read};
+use std::path::Path;
 use tui_textarea::TextArea;
 
+use crate::config;
+use crate::output;
+
+/// How the user left the full-screen editor, with the trimmed buffer.
+enum Exit {
+    Submit(String),
+    Cancel(String),
+}
+
+/// Open an empty editor. Text left in a cancelled session is saved as a
+/// draft and offered again here.
 pub fn open(title: &str) -> Result<Option<String>> {
+    let draft = config::draft_path();
+    let initial = restore_draft(&draft);
+    let result = edit(title, &initial, Some(&draft))?;
+    if result.is_some() {
+        fs::remove_file(&draft).ok();
+    }
+    Ok(result)
+}
+
+/// Open the editor pre-filled with `initial` text.
+pub fn open_with(title: &str, initial: &str) -> Result<Option<String>> {
+    edit(title, initial, None)
+}
+
+/// Falls back to a plain line-based prompt when the full-screen editor
+/// can't run.
+fn edit(title: &str, initial: &str, draft: Option<&Path>) -> Result<Option<String>> {
+    if !tui_supported() {
+        return open_plain(title, initial);
+    }
+    if let Err(e) = enter_tui() {
+        leave_tui();
+        output::verbose("Editor", &format!("{e:#}; using the plain prompt"));
+        return open_plain(title, initial);
+    }
+
+    let backend = ratatui::backend::CrosstermBackend::new(io::stdout());
+    let result = Terminal::new(backend)
+        .context("failed to create terminal")
+        .and_then(|mut terminal| run_editor(&mut terminal, title, initial, draft));
+
+    leave_tui();
+    match result? {
+        Exit::Submit(text) if text.is_empty() => Ok(None),
+        Exit::Submit(text) => Ok(Some(text)),
+        Exit::Cancel(text) => {
+            if let Some(path) = draft.filter(|_| !text.is_empty()) {
+                save_draft(path, &text)?;
+                output::status("Draft", &format!("saved to {}", path.display()));
+            }
+            Ok(None)
+        }
+    }
+}
+
+/// Ask whether to continue from a saved draft; declining keeps it on disk
+/// until the next submit.
+fn restore_draft(path: &Path) -> String {
+    let Ok(text) = fs::read_to_string(path) else {
+        return String::new();
+    };
+    let text = text.trim();
+    if text.is_empty() || !io::stdin().is_terminal() {
+        return String::new();
+    }
+    let words = text.split_whitespace().count();
+    if output::prompt_yn(
+        &format!("Restore the unsaved draft from last time ({words} words)?"),
+        true,
+    ) {
+        text.to_string()
+    } else {
+        String::new()
+    }
+}
+
+fn save_draft(path: &Path, text: &str) -> Result<()> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)
+            .with_context(|| format!("failed to create {}", parent.display()))?;
+    }
+    fs::write(path, text).with_context(|| format!("failed to write draft {}", path.display()))
+}
+
+/// The full-screen editor needs an interactive terminal on both ends that
+/// understands cursor movement.
+fn tui_supported() -> bool {
+    io::stdin().is_terminal()
+        && io::stdout().is_terminal()
+        && std::env::var("TERM").map_or(true, |term| term != "dumb")
+}
+
+fn enter_tui() -> Result<()> {
     terminal::enable_raw_mode().context("failed to enable raw mode")?;
     io::stdout()
         .execute(EnterAlternateScreen)
         .context("failed to enter alternate screen")?;
+    // Pastes arrive as a single event instead of one keystroke per character
+    io::stdout().execute(EnableBracketedPaste).ok();
+    Ok(())
+}
 
-    let backend = ratatui::backend::CrosstermBackend::new(io::stdout());
-    let mut terminal = Terminal::new(backend).context("failed to create terminal")?;
-
-    let result = run_editor(&mut terminal, title);
-
+fn leave_tui() {
+    io::stdout().execute(DisableBracketedPaste).ok();
     terminal::disable_raw_mode().ok();
     io::stdout().execute(LeaveAlternateScreen).ok();
+}
 
-    result
+/// Read the text from stdin until end of input. An empty answer keeps
+/// `initial`, or cancels when there is none.
+fn open_plain(title: &str, initial: &str) -> Result<Option<String>> {
+    let eof = if cfg!(windows) {
+        "Ctrl+Z then Enter"
+    } else {
+        "Ctrl+D"
+    };
+    eprintln!("{title}");
+    if initial.is_empty() {
+        eprintln!("Type the text, then press {eof} to finish (nothing cancels):");
+    } else {
+        eprintln!("Current text:\n\n{initial}\n");
+        eprintln!(
+            "Type a replacement, then press {eof} to finish (nothing keeps the current text):"
+        );
+    }
+
+    let mut text = String::new();
+    io::stdin()
+        .read_to_string(&mut text)
+        .context("failed to read text from stdin")?;
+    let text = text.trim();
+    if !text.is_empty() {
+        Ok(Some(text.to_string()))
+    } else if !initial.trim().is_empty() {
+        Ok(Some(initial.trim().to_string()))
+    } else {
+        Ok(None)
+    }
 }
 
 fn run_editor(
     terminal: &mut Terminal<ratatui::backend::CrosstermBackend<io::Stdout>>,
     title: &str,
-) -> Result<Option<String>> {
-    let mut textarea = TextArea::default();
-    textarea.set_block(
-        Block::default()
-            .borders(Borders::ALL)
-            .border_style(Style::default().fg(Color::Cyan))
-            .title(format!(" {title} ")),
-    );
+    initial: &str,
+    draft: Option<&Path>,
+) -> Result<Exit> {
+    let mut textarea = if initial.is_empty() {
+        TextArea::default()
+    } else {
+        TextArea::new(initial.lines().map(str::to_string).collect())
+    };
+    textarea.set_block(editor_block(title));
     textarea.set_cursor_line_style(Style::default().add_modifier(Modifier::UNDERLINED));
     textarea.set_line_number_style(Style::default().fg(Color::DarkGray));
 
@@ -48,7 +178,7 @@ fn run_editor(
 
             frame.render_widget(&textarea, chunks[0]);
 
-            let help = Paragraph::new(vec![Line::from(vec![
+            let mut keys = vec![
                 Span::styled(
                     " Ctrl+D ",
                     Style::default().fg(Color::Black).bg(Color::Cyan),
@@ -56,40 +186,95 @@ fn run_editor(
                 Span::raw(" Submit  "),
                 Span::styled(" Esc ", Style::default().fg(Color::Black).bg(Color::Red)),
                 Span::raw(" Cancel  "),
+                Span::styled(
+                    " Ctrl+Z/Y ",
+                    Style::default().fg(Color::Black).bg(Color::DarkGray),
+                ),
+                Span::raw(" Undo/Redo  "),
                 Span::styled(
                     " Enter ",
                     Style::default().fg(Color::Black).bg(Color::DarkGray),
                 ),
                 Span::raw(" New line"),
-            ])]);
+            ];
+            if draft.is_some() {
+                keys.push(Span::raw("  "));
+                keys.push(Span::styled(
+                    " Ctrl+S ",
+                    Style::default().fg(Color::Black).bg(Color::DarkGray),
+                ));
+                keys.push(Span::raw(" Save draft"));
+            }
+            let help = Paragraph::new(vec![Line::from(keys)]);
             frame.render_widget(help, chunks[1]);
         })?;
 
-        if let Event::Key(key) = event::read().context("failed to read input event")? {
-            match key {
+        match event::read().context("failed to read input event")? {
+            // Some terminals send bare CR line breaks in pastes
+            Event::Paste(text) => {
+                textarea.insert_str(text.replace("\r\n", "\n").replace('\r', "\n"));
+            }
+            Event::Key(key) => match key {
                 // Ctrl+D → submit
                 KeyEvent {
                     code: KeyCode::Char('d'),
                     modifiers: KeyModifiers::CONTROL,
                     ..
                 } => {
-                    let text = textarea.lines().join("\n").trim().to_string();
-                    if text.is_empty() {
-                        return Ok(None);
-                    }
-                    return Ok(Some(text));
+                    return Ok(Exit::Submit(buffer_text(&textarea)));
                 }
                 // Esc → cancel
                 KeyEvent {
                     code: KeyCode::Esc, ..
                 } => {
-                    return Ok(None);
+                    return Ok(Exit::Cancel(buffer_text(&textarea)));
+                }
+                // Ctrl+S → save a draft without leaving
+                KeyEvent {
+                    code: KeyCode::Char('s'),
+                    modifiers: KeyModifiers::CONTROL,
+                    ..
+                } => {
+                    if let Some(path) = draft {
+                        let note = match save_draft(path, &buffer_text(&textarea)) {
+                            Ok(()) => "draft saved".to_string(),
+                            Err(e) => format!("draft not saved: {e:#}"),
+                        };
+                        textarea.set_block(editor_block(&format!("{title} ({note})")));
+                    }
+                }
+                // Ctrl+Z → undo, Ctrl+Y → redo
+                KeyEvent {
+                    code: KeyCode::Char('z'),
+                    modifiers: KeyModifiers::CONTROL,
+                    ..
+                } => {
+                    textarea.undo();
+                }
+                KeyEvent {
+                    code: KeyCode::Char('y'),
+                    modifiers: KeyModifiers::CONTROL,
+                    ..
+                } => {
+                    textarea.redo();
                 }
                 // Everything else → forward to textarea
                 input => {
                     textarea.input(input);
                 }
-            }
+            },
+            _ => {}
         }
     }
 }
+
+fn editor_block(title: &str) -> Block<'static> {
+    Block::default()
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::Cyan))
+        .title(format!(" {title} "))
+}
+
+fn buffer_text(textarea: &TextArea) -> String {
+    textarea.lines().join("\n").trim().to_string()
+}
@@ -0,0 +1,74 @@
+use std::fmt;
+
+/// Error categories reported through the process exit code, so scripts can
+/// tell a bad setting from a missing model or a failed generation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ErrorKind {
+    /// Invalid configuration or command-line usage
+    Config,
+    /// Model not installed, incomplete, or could not be fetched
+    Model,
+    /// The Python backend or an external tool failed
+    Backend,
+    /// Reading or writing files failed
+    Io,
+}
+
+impl ErrorKind {
+    pub fn exit_code(self) -> i32 {
+        match self {
+            ErrorKind::Config => 2,
+            ErrorKind::Model => 3,
+            ErrorKind::Backend => 4,
+            ErrorKind::Io => 5,
+        }
+    }
+
+    /// Build an error tagged with this category.
+    pub fn error(self, message: impl Into<String>) -> anyhow::Error {
+        anyhow::Error::new(Categorized {
+            kind: self,
+            message: message.into(),
+        })
+    }
+}
+
+#[derive(Debug)]
+struct Categorized {
+    kind: ErrorKind,
+    message: String,
+}
+
+impl fmt::Display for Categorized {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.message)
+    }
+}
+
+impl std::error::Error for Categorized {}
+
+/// Category of an error: an explicit tag anywhere in the chain wins, then
+/// well-known source errors are classified. `None` means a general failure.
+pub fn classify(err: &anyhow::Error) -> Option<ErrorKind> {
+    if let Some(tagged) = err.chain().find_map(|e| e.downcast_ref::<Categorized>()) {
+        return Some(tagged.kind);
+    }
+    err.chain().find_map(|e| {
+        if e.is::<toml::de::Error>()
+            || e.is::<std::num::ParseIntError>()
+            || e.is::<std::num::ParseFloatError>()
+            || e.is::<std::str::ParseBoolError>()
+        {
+            Some(ErrorKind::Config)
+        } else if e.is::<std::io::Error>() || e.is::<hound::Error>() {
+            Some(ErrorKind::Io)
+        } else {
+            None
+        }
+    })
+}
+
+/// Process exit code for an error returned from `run`.
+pub fn exit_code(err: &anyhow::Error) -> i32 {
+    classify(err).map_or(1, ErrorKind::exit_code)
+}
@@ -1,14 +1,23 @@
 use std::fs;
 use std::path::{Path, PathBuf};
 use std::process::Command;
-use std::time::SystemTime;
+use std::sync::OnceLock;
+use std::time::{Duration, Instant, SystemTime};
 
+use crate::audio;
 use crate::config::{self, Config};
 use crate::editor;
+use crate::error::ErrorKind;
 use crate::models;
+use crate::normalize;
 use crate::output;
-use crate::platform::Backend;
+use crate::platform::{self, Backend};
+use crate::progress::{self, Event};
+use crate::stats;
+use crate::voices;
+use crate::warm;
 use anyhow::{Context, Result};
+use serde::Serialize;
 
 fn cleanup_old_outputs(cfg: &Config) {
     if !cfg.auto_cleanup {
@@ -55,81 +64,418 @@ fn cleanup_old_outputs(cfg: &Config) {
 
 pub struct SpeakArgs {
     pub text: Option<String>,
-    pub file: Option<String>,
+    pub file: Vec<String>,
+    /// Text file with one item per line, each generated into its own file
+    pub batch: Option<String>,
     pub voice: Option<String>,
     pub emotion: Option<String>,
+    pub instruct: Option<String>,
+    pub file_pause: Option<u32>,
     pub speed: Option<f32>,
+    /// Pitch shift in semitones, for backends that support it
+    pub pitch: Option<f32>,
     pub output: Option<String>,
+    /// Output format for paths without an extension (falls back to `default_format`)
+    pub format: Option<String>,
+    /// Existing WAV file the new audio is appended to, instead of a new output
+    pub append: Option<String>,
+    /// Keep the backend's chunk files in a directory instead of one joined file
+    pub no_join: bool,
+    /// Only report the expected size of the batch outputs
+    pub estimate: bool,
+    /// Rewriting of the input text before generation
+    pub normalize: normalize::Options,
+    pub post: PostProcess,
 }
 
 pub struct DesignArgs {
     pub description: String,
     pub text: Option<String>,
-    pub file: Option<String>,
+    pub file: Vec<String>,
     pub speed: Option<f32>,
+    /// Pitch shift in semitones, for backends that support it
+    pub pitch: Option<f32>,
     pub output: Option<String>,
+    /// Output format for paths without an extension (falls back to `default_format`)
+    pub format: Option<String>,
+    /// Existing WAV file the new audio is appended to, instead of a new output
+    pub append: Option<String>,
+    /// Keep the backend's chunk files in a directory instead of one joined file
+    pub no_join: bool,
+    /// Send the description as it is, ignoring `design_prompt`
+    pub raw: bool,
+    /// Rewriting of the input text before generation
+    pub normalize: normalize::Options,
+    pub post: PostProcess,
 }
 
 pub struct CloneArgs {
-    pub ref_audio: Option<String>,
+    /// Reference clips; several are merged into one before cloning
+    pub ref_audio: Vec<String>,
     pub ref_text: Option<String>,
+    pub auto_transcript: bool,
     pub voice: Option<String>,
     pub text: Option<String>,
-    pub file: Option<String>,
+    pub file: Vec<String>,
     pub speed: Option<f32>,
+    /// Pitch shift in semitones, for backends that support it
+    pub pitch: Option<f32>,
     pub output: Option<String>,
+    /// Output format for paths without an extension (falls back to `default_format`)
+    pub format: Option<String>,
+    /// Existing WAV file the new audio is appended to, instead of a new output
+    pub append: Option<String>,
+    /// Keep the backend's chunk files in a directory instead of one joined file
+    pub no_join: bool,
+    /// Model variant for this run, over the saved voice's preferred one
+    pub variant: Option<String>,
+    /// Rewriting of the input text before generation
+    pub normalize: normalize::Options,
+    pub post: PostProcess,
 }
 
-fn resolve_text(text: Option<&str>, file: Option<&str>) -> Result<String> {
-    match (text, file) {
-        (Some(t), _) => Ok(t.to_string()),
-        (None, Some(f)) => {
-            let path = config::expand_path(f);
-            fs::read_to_string(&path)
-                .with_context(|| format!("failed to read text file: {}", path.display()))
+/// Speaker names are free-form, so an unknown name is not an error; but one
+/// that is a near miss of the default, a saved, or a built-in voice is
+/// probably a typo.
+fn warn_voice_typo(cfg: &Config, voice: &str) {
+    let mut known = voices::saved_names(cfg);
+    known.push(cfg.default_voice.clone());
+    known.extend(voices::cached_builtin_names(cfg));
+    if known.iter().any(|k| k.eq_ignore_ascii_case(voice)) {
+        return;
+    }
+    if let Some(close) = voices::suggest(voice, &known) {
+        output::warn(&format!("unknown voice '{voice}'; did you mean '{close}'?"));
+    }
+}
+
+/// Error for a command run without any text. `text_usage` is how the
+/// command takes inline text, e.g. `"<TEXT>"` or `--text <TEXT>`.
+fn missing_text(text_usage: &str) -> anyhow::Error {
+    ErrorKind::Config.error(format!(
+        "no text provided; pass {text_usage} or --file <PATH>"
+    ))
+}
+
+/// Resolve input text: one entry per `--file`, or a single entry from the
+/// inline text or the editor. The editor is only offered on a terminal.
+/// Each entry is normalized with `normalize`.
+fn resolve_texts(
+    text: Option<&str>,
+    files: &[String],
+    text_usage: &str,
+    normalize: &normalize::Options,
+) -> Result<Vec<String>> {
+    let texts = read_texts(text, files, text_usage)?;
+    Ok(texts
+        .iter()
+        .map(|text| normalize_text(normalize, text))
+        .collect())
+}
+
+/// `normalize` applied to `text`, showing the result with `--verbose`.
+fn normalize_text(normalize: &normalize::Options, text: &str) -> String {
+    let normalized = normalize.apply(text);
+    if normalized != text {
+        output::verbose("Normalized", &normalized);
+    }
+    normalized
+}
+
+fn read_texts(text: Option<&str>, files: &[String], text_usage: &str) -> Result<Vec<String>> {
+    use std::io::IsTerminal;
+
+    match (text, files) {
+        (Some(t), _) if t.trim().is_empty() => Err(missing_text(text_usage)),
+        (Some(t), _) => Ok(vec![t.to_string()]),
+        (None, []) if !(std::io::stdin().is_terminal() && std::io::stdout().is_terminal()) => {
+            Err(missing_text(text_usage))
         }
-        (None, None) => {
+        (None, []) => {
             // Open TUI editor for multi-line input
             match editor::open("Enter text (multi-line)")? {
-                Some(t) if !t.is_empty() => Ok(t),
-                _ => anyhow::bail!("no text provided (editor cancelled)"),
+                Some(t) if !t.is_empty() => Ok(vec![t]),
+                _ => Err(ErrorKind::Config.error("no text provided (editor cancelled)")),
             }
         }
+        (None, files) => files
+            .iter()
+            .map(|f| {
+                let path = config::expand_path(f);
+                read_text_file(&path)
+            })
+            .collect(),
+    }
+}
+
+/// Read a text file in any common encoding. UTF-8 (with or without a BOM)
+/// and UTF-16 with a BOM are read as is; anything else is decoded with the
+/// most likely legacy encoding, such as Windows-1252, with a warning.
+pub(crate) fn read_text_file(path: &Path) -> Result<String> {
+    let bytes =
+        fs::read(path).with_context(|| format!("failed to read text file: {}", path.display()))?;
+    if let Some((encoding, bom_len)) = encoding_rs::Encoding::for_bom(&bytes) {
+        let (text, had_errors) = encoding.decode_without_bom_handling(&bytes[bom_len..]);
+        if !had_errors {
+            return Ok(text.into_owned());
+        }
+    }
+    let invalid = match std::str::from_utf8(&bytes) {
+        Ok(text) => return Ok(text.to_string()),
+        Err(e) => e.valid_up_to(),
+    };
+    let not_text = |offset: usize| {
+        let shown: Vec<String> = bytes[offset..]
+            .iter()
+            .take(4)
+            .map(|b| format!("0x{b:02X}"))
+            .collect();
+        ErrorKind::Config.error(format!(
+            "could not decode {}: unexpected bytes {} at offset {offset}; \
+             save it as UTF-8 text, e.g. with `iconv -t UTF-8`",
+            path.display(),
+            shown.join(" ")
+        ))
+    };
+    // Every legacy encoding decodes something; NUL bytes mean it isn't text
+    if let Some(offset) = bytes.iter().position(|&b| b == 0) {
+        return Err(not_text(offset));
     }
+
+    let mut detector = chardetng::EncodingDetector::new();
+    detector.feed(&bytes, true);
+    let encoding = detector.guess(None, true);
+    let (text, had_errors) = encoding.decode_without_bom_handling(&bytes);
+    if had_errors || encoding == encoding_rs::UTF_8 {
+        return Err(not_text(invalid));
+    }
+    output::warn(&format!(
+        "{} is not UTF-8; read it as {}",
+        path.display(),
+        encoding.name()
+    ));
+    Ok(text.into_owned())
+}
+
+fn join_texts(texts: &[String]) -> String {
+    texts
+        .iter()
+        .map(|t| t.trim())
+        .collect::<Vec<_>>()
+        .join("\n\n")
 }
 
-fn resolve_output(output: Option<&str>, cfg: &Config) -> PathBuf {
-    match output {
-        Some(p) => config::expand_path(p),
+/// Resolve input text, joining multiple files with blank lines.
+fn resolve_text(
+    text: Option<&str>,
+    files: &[String],
+    text_usage: &str,
+    normalize: &normalize::Options,
+) -> Result<String> {
+    Ok(join_texts(&resolve_texts(
+        text, files, text_usage, normalize,
+    )?))
+}
+
+/// `--output` with a path. A `--output-dir` next to it would be ignored, so
+/// the two are rejected together: `--output-dir` alone keeps the automatic
+/// file name in another directory.
+fn explicit_output(path: &str) -> Result<PathBuf> {
+    if config::output_dir_overridden() {
+        return Err(ErrorKind::Config.error(
+            "--output and --output-dir cannot be combined: --output names the full path, \
+             while --output-dir picks the directory and keeps the automatic file name",
+        ));
+    }
+    Ok(config::expand_path(path))
+}
+
+fn resolve_output(output: Option<&str>, format: Option<&str>, cfg: &Config) -> Result<PathBuf> {
+    let mut path = match output {
+        Some(p) => explicit_output(p)?,
         None => {
             let dir = config::expand_path(&cfg.output_dir);
-            let ts = std::time::SystemTime::now()
+            let now = std::time::SystemTime::now()
                 .duration_since(std::time::UNIX_EPOCH)
-                .unwrap()
-                .as_secs();
-            dir.join(format!("tts_{ts}"))
+                .unwrap();
+            // Millisecond timestamp plus a suffix mixed from the sub-millisecond
+            // clock and the process id, so parallel runs never share a name.
+            let suffix = (now.subsec_nanos() ^ std::process::id().rotate_left(16)) & 0xffff;
+            dir.join(format!("tts_{}_{suffix:04x}", now.as_millis()))
+        }
+    };
+    audio::validate_output_format(&path)?;
+    if let (Some(flag), Some(ext)) = (format, audio::extension(&path)) {
+        if flag.trim_start_matches('.').to_lowercase() != ext {
+            return Err(ErrorKind::Config.error(format!(
+                "--format {flag} conflicts with the output extension .{ext}"
+            )));
+        }
+    }
+    // Only paths without an extension take the requested or default format
+    if let Some(format) = format.or(cfg.default_format.as_deref()) {
+        let format = format.trim_start_matches('.').to_lowercase();
+        audio::validate_format(&format)?;
+        if path.extension().is_none() {
+            path.set_extension(format);
         }
     }
+    if output.is_none() {
+        config::ensure_writable(&config::expand_path(&cfg.output_dir), "output_dir")?;
+        return Ok(unique_output_path(path));
+    }
+    check_output_path(&path)
+}
+
+/// `path`, or the first free `<stem>_<n>` next to it. Generated names only
+/// repeat when the system clock went backwards, so that is reported rather
+/// than overwriting the earlier output.
+fn unique_output_path(path: PathBuf) -> PathBuf {
+    if !path.exists() {
+        return path;
+    }
+    let stem = path
+        .file_stem()
+        .map(|s| s.to_string_lossy().to_string())
+        .unwrap_or_default();
+    let ext = path
+        .extension()
+        .map(|e| format!(".{}", e.to_string_lossy()))
+        .unwrap_or_default();
+    let free = (1..)
+        .map(|n| path.with_file_name(format!("{stem}_{n}{ext}")))
+        .find(|p| !p.exists())
+        .expect("some numbered name is free");
+    output::warn(&format!(
+        "{} already exists, probably because the system clock moved backwards; \
+         writing {} instead",
+        path.display(),
+        free.display()
+    ));
+    free
+}
+
+/// Fail on an unusable `--output` before the model is loaded: its directory
+/// must be creatable and writable, and a file path must not name an
+/// existing directory. Returns the path with its directory made absolute.
+fn check_output_path(path: &Path) -> Result<PathBuf> {
+    if path.extension().is_some() && path.is_dir() {
+        return Err(ErrorKind::Config.error(format!(
+            "--output {} is a directory; give a file path such as {}",
+            path.display(),
+            path.join("speech.wav").display()
+        )));
+    }
+    let parent = path
+        .parent()
+        .filter(|p| !p.as_os_str().is_empty())
+        .unwrap_or(Path::new("."));
+    config::ensure_writable_with_hint(parent, "choose another --output path")?;
+    let parent = parent
+        .canonicalize()
+        .with_context(|| format!("cannot resolve {}", parent.display()))?;
+    Ok(match path.file_name() {
+        Some(name) => parent.join(name),
+        None => parent,
+    })
+}
+
+/// Check the file named by `--append` before generating anything: it must
+/// be an existing, readable WAV file in a format new audio can be written
+/// in.
+fn append_target(path: &str) -> Result<PathBuf> {
+    let target = config::expand_path(path);
+    if !target.is_file() {
+        return Err(ErrorKind::Config.error(format!(
+            "cannot append to {}: file not found",
+            target.display()
+        )));
+    }
+    if audio::extension(&target).as_deref() != Some("wav") {
+        return Err(ErrorKind::Config.error(format!(
+            "cannot append to {}: only .wav files can be appended to",
+            target.display()
+        )));
+    }
+    let spec =
+        audio::spec(&target).with_context(|| format!("cannot append to {}", target.display()))?;
+    if !audio::is_writable(spec) {
+        return Err(ErrorKind::Config.error(format!(
+            "cannot append to {}: unsupported WAV format ({} channel(s), {}-bit)",
+            target.display(),
+            spec.channels,
+            spec.bits_per_sample
+        )));
+    }
+    Ok(target)
+}
+
+/// Temporary WAV next to `target` that new audio is generated into.
+fn append_staging(target: &Path) -> PathBuf {
+    let stem = target
+        .file_stem()
+        .map(|s| s.to_string_lossy().to_string())
+        .unwrap_or_default();
+    target.with_file_name(format!(".{stem}.append-{}.wav", std::process::id()))
+}
+
+/// Concatenate `generated` onto the end of `target`, replacing `target` with
+/// a rename. New audio at a different sample rate, channel count or sample
+/// format is converted to match.
+fn append_audio(cfg: &Config, target: &Path, generated: &Path) -> Result<PathBuf> {
+    let mut temp = TempWorkspace::new();
+    temp.track(generated.to_path_buf());
+    let joined = temp.track(target.with_file_name(format!(
+        ".{}.joined-{}.wav",
+        target
+            .file_stem()
+            .map(|s| s.to_string_lossy().to_string())
+            .unwrap_or_default(),
+        std::process::id()
+    )));
+    let target_spec = audio::spec(target)?;
+    if audio::sample_rate(generated)? != target_spec.sample_rate {
+        let resampled = temp.track(generated.with_extension("resampled.wav"));
+        audio::resample(generated, &resampled, target_spec.sample_rate)?;
+        fs::rename(&resampled, generated)
+            .with_context(|| format!("failed to replace {}", generated.display()))?;
+    }
+    if audio::spec(generated)? != target_spec {
+        let converted = temp.track(generated.with_extension("converted.wav"));
+        audio::convert_layout(generated, &converted, target_spec)?;
+        fs::rename(&converted, generated)
+            .with_context(|| format!("failed to replace {}", generated.display()))?;
+    }
+    audio::concat_wavs(
+        &[target.to_path_buf(), generated.to_path_buf()],
+        &[],
+        0,
+        &joined,
+    )
+    .with_context(|| format!("cannot append to {}", target.display()))?;
+    fs::rename(&joined, target)
+        .with_context(|| format!("failed to update {}", target.display()))?;
+    config::apply_output_mode(cfg, target)?;
+    Ok(target.to_path_buf())
 }
 
 /// Find the actual audio file produced by mlx_audio.
-/// With --join_audio it creates a single audio.wav inside the output directory.
-fn find_output_file(output_dir: &Path) -> Option<PathBuf> {
+/// With --join_audio it creates a single audio.wav inside the output directory;
+/// a directory holding one WAV (possibly in a per-chunk subfolder) gives that
+/// file. A directory of several chunks gives `None` until `join_chunks` has
+/// joined them.
+pub(crate) fn find_output_file(output_dir: &Path) -> Option<PathBuf> {
     if output_dir.is_dir() {
         // --join_audio produces audio.wav in the directory
         let joined = output_dir.join("audio.wav");
         if joined.exists() {
             return Some(joined);
         }
-        // Fallback: first wav file found
-        let mut wavs: Vec<_> = fs::read_dir(output_dir)
-            .ok()?
-            .filter_map(|e| e.ok())
-            .map(|e| e.path())
-            .filter(|p| p.extension().and_then(|e| e.to_str()) == Some("wav"))
-            .collect();
-        wavs.sort();
-        wavs.into_iter().next()
+        let wavs = chunk_wavs(output_dir);
+        match wavs.as_slice() {
+            [single] => Some(single.clone()),
+            _ => None,
+        }
     } else if output_dir.exists() {
         Some(output_dir.to_path_buf())
     } else {
@@ -137,94 +483,799 @@ fn find_output_file(output_dir: &Path) -> Option<PathBuf> {
     }
 }
 
+/// The single audio file of a backend run's output, first joining a
+/// directory of chunk WAVs into its `audio.wav`, overlapping them by
+/// `crossfade_ms`.
+pub(crate) fn join_chunks(output_dir: &Path, crossfade_ms: u32) -> Result<PathBuf> {
+    if let Some(file) = find_output_file(output_dir) {
+        return Ok(file);
+    }
+    let wavs = chunk_wavs(output_dir);
+    if wavs.is_empty() {
+        return Err(ErrorKind::Backend.error("TTS produced no audio"));
+    }
+    let joined = output_dir.join("audio.wav");
+    audio::concat_wavs(&wavs, &[], crossfade_ms, &joined)
+        .inspect_err(|_| {
+            fs::remove_file(&joined).ok();
+        })
+        .context("could not join audio chunks")?;
+    Ok(joined)
+}
+
+/// The audio files of a backend run's output in order: its single file, or
+/// each chunk of a directory that hasn't been joined.
+fn output_chunks(output: &Path) -> Vec<PathBuf> {
+    match find_output_file(output) {
+        Some(file) => vec![file],
+        None if output.is_dir() => chunk_wavs(output),
+        None => Vec::new(),
+    }
+}
+
+/// The chunk WAVs under `dir`, possibly nested in per-chunk subfolders, in
+/// name order.
+fn chunk_wavs(dir: &Path) -> Vec<PathBuf> {
+    let mut wavs = Vec::new();
+    collect_wavs(dir, &mut wavs);
+    wavs.sort();
+    wavs
+}
+
+/// Recursively collect `.wav` files under `dir`.
+fn collect_wavs(dir: &Path, wavs: &mut Vec<PathBuf>) {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return;
+    };
+    for path in entries.filter_map(|e| e.ok()).map(|e| e.path()) {
+        if path.is_dir() {
+            collect_wavs(&path, wavs);
+        } else if path.extension().and_then(|e| e.to_str()) == Some("wav") {
+            wavs.push(path);
+        }
+    }
+}
+
+/// On CUDA, warn (once per run) when the configured variant likely won't
+/// fit in the free GPU memory, suggesting a smaller variant if one would.
+fn warn_low_vram(cfg: &Config) {
+    static CHECKED: std::sync::Once = std::sync::Once::new();
+    if cfg.backend != Backend::Cuda {
+        return;
+    }
+    CHECKED.call_once(|| {
+        let Some(free) = platform::gpu_free_memory_mib() else {
+            return;
+        };
+        let Some(need) = models::estimated_vram_mib(cfg.backend, &cfg.model_variant) else {
+            return;
+        };
+        // Parallel jobs each load their own copy of the model
+        let need = need * cfg.max_jobs as u64;
+        if need <= free {
+            return;
+        }
+        let smaller = models::fitting_variant(cfg.backend, &cfg.model_variant, free)
+            .filter(|v| *v != cfg.model_variant);
+        let hint = match smaller {
+            _ if cfg.max_jobs > 1 => "lower --jobs or max_jobs".to_string(),
+            Some(v) => format!("try `qwen-tts config set model_variant {v}`"),
+            None => "close other GPU programs or use `--backend cpu`".to_string(),
+        };
+        output::warn(&format!(
+            "model '{}' needs about {need} MiB of GPU memory but only {free} MiB is free; \
+             generation may run out of memory — {hint}",
+            cfg.model_variant
+        ));
+    });
+}
+
 /// Returns the model path or repo ID. Prefers local, downloads if missing.
-fn model_id(cfg: &Config) -> Result<String> {
+pub(crate) fn model_id(cfg: &Config) -> Result<String> {
+    models::repo_id(cfg.backend, &cfg.model_variant).with_context(|| {
+        format!(
+            "invalid model_variant in {}",
+            config::config_path().display()
+        )
+    })?;
+    warn_low_vram(cfg);
     let local = config::expand_path(&cfg.models_dir).join(&cfg.model_variant);
-    if local.exists() {
+    if models::is_model_installed(cfg, &cfg.model_variant) {
         return Ok(local.to_string_lossy().to_string());
     }
-    // Model not installed — download it now
-    output::status("Model", "not found locally, downloading...");
-    models::download(&cfg.model_variant)?;
+    let incomplete = local
+        .exists()
+        .then(|| models::incomplete_reason(&local))
+        .flatten();
+    if !cfg.auto_download {
+        let state = match &incomplete {
+            Some(reason) => format!("is incomplete ({reason})"),
+            None => "is not installed".to_string(),
+        };
+        return Err(ErrorKind::Model.error(format!(
+            "model '{}' {state} and automatic downloads are disabled\n\
+             Run `qwen-tts models download --variant {}` first",
+            cfg.model_variant, cfg.model_variant
+        )));
+    }
+    match incomplete {
+        Some(reason) => output::status(
+            "Model",
+            &format!("incomplete ({reason}), re-downloading..."),
+        ),
+        // Model not installed — download it now
+        None => output::status("Model", "not found locally, downloading..."),
+    }
+    models::download(&cfg.model_variant, None)?;
     Ok(local.to_string_lossy().to_string())
 }
 
-pub fn speak(args: SpeakArgs) -> Result<()> {
-    let cfg = config::load()?;
-    cleanup_old_outputs(&cfg);
-    let text = resolve_text(args.text.as_deref(), args.file.as_deref())?;
-    let out = resolve_output(args.output.as_deref(), &cfg);
-    let voice = args.voice.as_deref().unwrap_or(&cfg.default_voice);
-    let speed = args.speed.unwrap_or(cfg.default_speed);
+/// Emotion tags recognized inline in `speak` text, e.g. `[happy] Great news!`.
+/// `[neutral]` resets to the emotion given by `--emotion` (if any).
+const EMOTION_TAGS: &[&str] = &[
+    "happy",
+    "sad",
+    "angry",
+    "excited",
+    "calm",
+    "serious",
+    "cheerful",
+    "fearful",
+    "surprised",
+    "whisper",
+    "neutral",
+];
 
-    // Build instruct text for voice personality
-    let instruct = match &args.emotion {
+struct Segment {
+    emotion: Option<String>,
+    text: String,
+    /// Silence inserted after this segment when concatenating
+    pause_after_ms: u32,
+}
+
+fn push_segment(segments: &mut Vec<Segment>, emotion: Option<String>, text: &mut String) {
+    let trimmed = text.trim();
+    if !trimmed.is_empty() {
+        segments.push(Segment {
+            emotion,
+            text: trimmed.to_string(),
+            pause_after_ms: 0,
+        });
+    }
+    text.clear();
+}
+
+/// Split text on inline `[emotion]` tags. Unknown tags are kept as plain text.
+fn parse_emotion_segments(text: &str) -> Vec<Segment> {
+    let mut segments = Vec::new();
+    let mut emotion: Option<String> = None;
+    let mut current = String::new();
+    let mut rest = text;
+
+    while let Some(start) = rest.find('[') {
+        let Some(len) = rest[start..].find(']') else {
+            break;
+        };
+        let end = start + len;
+        let tag = &rest[start + 1..end];
+        current.push_str(&rest[..start]);
+
+        let key = tag.trim().to_lowercase();
+        if EMOTION_TAGS.contains(&key.as_str()) {
+            push_segment(&mut segments, emotion.take(), &mut current);
+            if key != "neutral" {
+                emotion = Some(key);
+            }
+        } else {
+            output::warn(&format!("unknown emotion tag [{tag}], treating as text"));
+            current.push_str(&rest[start..=end]);
+        }
+        rest = &rest[end + 1..];
+    }
+
+    current.push_str(rest);
+    push_segment(&mut segments, emotion, &mut current);
+    segments
+}
+
+/// Longest `--emotion` accepted; anything longer is a prompt, not a style cue.
+const MAX_EMOTION_CHARS: usize = 60;
+
+/// Reject oversized or multi-line `--emotion` values and warn when one reads
+/// like a sentence rather than a short style cue.
+fn validate_emotion(emotion: &str) -> Result<()> {
+    let examples = EMOTION_TAGS[..EMOTION_TAGS.len() - 1].join(", ");
+    if emotion.chars().count() > MAX_EMOTION_CHARS {
+        return Err(ErrorKind::Config.error(format!(
+            "--emotion is too long ({} chars, max {MAX_EMOTION_CHARS}); use a short style cue \
+             such as: {examples}\nFor a full prompt use --instruct instead",
+            emotion.chars().count()
+        )));
+    }
+    if emotion.chars().any(char::is_control) {
+        return Err(
+            ErrorKind::Config.error("--emotion must be a single line without control characters")
+        );
+    }
+
+    let words = emotion.split_whitespace().count();
+    let sentence_like = emotion.trim_end().ends_with(['.', '!', '?']);
+    if words > 4 || sentence_like {
+        output::warn(&format!(
+            "--emotion \"{emotion}\" looks like a sentence; short cues ({examples}) work best"
+        ));
+    }
+    Ok(())
+}
+
+/// Instruct for `speak`: the raw `--instruct` text if given, otherwise a
+/// template built from the voice and emotion.
+fn speak_instruct(raw: Option<&str>, voice: &str, emotion: Option<&str>) -> String {
+    if let Some(raw) = raw {
+        return raw.to_string();
+    }
+    match emotion {
         Some(emo) => format!("Speak as {voice} with {emo} emotion."),
         None => format!("Speak as {voice}."),
-    };
+    }
+}
 
-    output::status("Generating", &format!("speech with {voice} voice..."));
+/// Instruction for `design`: the description wrapped in `design_prompt`,
+/// or verbatim without one or with `--raw`.
+fn design_instruct(cfg: &Config, description: &str, raw: bool) -> String {
+    match &cfg.design_prompt {
+        Some(template) if !raw => template.replace("{description}", description),
+        _ => description.to_string(),
+    }
+}
 
-    let status = run_tts_command(
-        &cfg,
-        &TtsParams {
-            text: &text,
+/// Where concatenated segments are written: `out` itself when it names a file,
+/// otherwise `audio.wav` inside it (matching the `--join_audio` layout).
+fn joined_output_path(out: &Path) -> Result<PathBuf> {
+    if out.extension().is_some() {
+        return Ok(out.to_path_buf());
+    }
+    fs::create_dir_all(out).with_context(|| format!("failed to create {}", out.display()))?;
+    Ok(out.join("audio.wav"))
+}
+
+/// Generate each emotion segment under `dir`, giving the audio files of
+/// each: one, or several chunks when the backend didn't join them.
+fn generate_segments(
+    cfg: &Config,
+    segments: &[Segment],
+    default_emotion: Option<&str>,
+    raw_instruct: Option<&str>,
+    voice: &str,
+    prosody: Prosody,
+    dir: &Path,
+) -> Result<Vec<Vec<PathBuf>>> {
+    let word_count = |text: &str| text.split_whitespace().count() as u64;
+    let progress = output::word_progress(segments.iter().map(|s| word_count(&s.text)).sum());
+    let jobs = cfg.max_jobs.clamp(1, segments.len().max(1));
+
+    if jobs > 1 {
+        // Resolve the model and script once, before workers race to install them
+        if cfg.backend != Backend::Custom {
+            model_id(cfg)?;
+        }
+        if matches!(cfg.backend, Backend::Cuda | Backend::Cpu) && cfg.compat_script.is_none() {
+            config::install_compat_script()?;
+        }
+    }
+
+    let run_segment = |i: usize| -> Result<Vec<PathBuf>> {
+        let segment = &segments[i];
+        let emotion = segment.emotion.as_deref().or(default_emotion);
+        let instruct = speak_instruct(raw_instruct, voice, emotion);
+        let seg_out = dir.join(format!("segment_{i:03}"));
+        let label = format!(
+            "{}/{} ({})",
+            i + 1,
+            segments.len(),
+            emotion.unwrap_or("neutral")
+        );
+        let params = TtsParams {
+            text: &segment.text,
             instruct: &instruct,
-            speed,
-            output_path: &out,
+            speed: prosody.speed,
+            pitch: prosody.pitch,
+            output_path: &seg_out,
             ref_audio: None,
             ref_text: None,
             voice: Some(voice),
-        },
+            join: true,
+        };
+
+        // With one job the backend's output gets the terminal to itself;
+        // suspending for parallel jobs would serialize them on the bar's lock.
+        let result = if jobs == 1 {
+            progress.set_message(format!("segment {}/{}", i + 1, segments.len()));
+            progress.suspend(|| {
+                output::status("Segment", &label);
+                run_tts_command(cfg, &params)
+            })
+        } else {
+            progress.suspend(|| output::status("Segment", &label));
+            run_tts_command(cfg, &params)
+        };
+        result.with_context(|| format!("segment {} failed", i + 1))?;
+
+        let chunks = output_chunks(&seg_out);
+        if chunks.is_empty() {
+            anyhow::bail!("no audio produced for segment {}", i + 1);
+        }
+        progress.inc(word_count(&segment.text));
+        Ok(chunks)
+    };
+
+    let results = run_pool(segments.len(), jobs, run_segment);
+    let parts = results.into_iter().collect::<Result<Vec<_>>>();
+    match &parts {
+        Ok(_) => progress.finish_with_message("done"),
+        Err(_) => progress.abandon(),
+    }
+    parts
+}
+
+/// Run `task(0..count)` on up to `jobs` worker threads and return the
+/// results in index order. After a failure no new tasks are started; tasks
+/// that never ran report a cancellation error.
+fn run_pool<T, F>(count: usize, jobs: usize, task: F) -> Vec<Result<T>>
+where
+    T: Send,
+    F: Fn(usize) -> Result<T> + Sync,
+{
+    use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+    use std::sync::Mutex;
+
+    let next = AtomicUsize::new(0);
+    let failed = AtomicBool::new(false);
+    let results: Mutex<Vec<Option<Result<T>>>> = Mutex::new((0..count).map(|_| None).collect());
+
+    std::thread::scope(|scope| {
+        for _ in 0..jobs.max(1) {
+            scope.spawn(|| loop {
+                if failed.load(Ordering::SeqCst) {
+                    break;
+                }
+                let i = next.fetch_add(1, Ordering::SeqCst);
+                if i >= count {
+                    break;
+                }
+                let result = task(i);
+                if result.is_err() {
+                    failed.store(true, Ordering::SeqCst);
+                }
+                results.lock().unwrap()[i] = Some(result);
+            });
+        }
+    });
+
+    results
+        .into_inner()
+        .unwrap()
+        .into_iter()
+        .enumerate()
+        .map(|(i, r)| r.unwrap_or_else(|| Err(anyhow::anyhow!("segment {} cancelled", i + 1))))
+        .collect()
+}
+
+pub fn speak(args: SpeakArgs) -> Result<()> {
+    let cfg = config::load()?;
+    if let Some(emotion) = &args.emotion {
+        validate_emotion(emotion)?;
+    }
+    if let Some(secs) = args.post.max_secs {
+        if !(secs.is_finite() && secs > 0.0) {
+            return Err(ErrorKind::Config.error(format!(
+                "--max-seconds must be a positive number, got {secs}"
+            )));
+        }
+    }
+    if let Some(batch) = &args.batch {
+        if args.estimate {
+            return estimate_batch(&cfg, &args, batch);
+        }
+        cleanup_old_outputs(&cfg);
+        return speak_batch(&cfg, &args, batch);
+    }
+    cleanup_old_outputs(&cfg);
+    let append = args.append.as_deref().map(append_target).transpose()?;
+    // Checked before the editor can open, so typed text isn't lost to a bad path
+    let out = match &append {
+        Some(target) => append_staging(target),
+        None if args.no_join => resolve_chunk_dir(args.output.as_deref(), &cfg)?,
+        None => resolve_output(args.output.as_deref(), args.format.as_deref(), &cfg)?,
+    };
+    let texts = resolve_texts(
+        args.text.as_deref(),
+        &args.file,
+        "the text as an argument",
+        &args.normalize.with_defaults(&cfg),
     )?;
+    let post = args.post.with_defaults(&cfg);
+    let voice = args.voice.as_deref().unwrap_or(&cfg.default_voice);
+    warn_voice_typo(&cfg, voice);
+    let prosody = Prosody::resolve(&cfg, args.speed, args.pitch)?;
 
-    if !status.success() {
-        anyhow::bail!("TTS generation failed");
+    // With --file-pause, each file becomes its own run of segments followed by silence
+    let segments = match args.file_pause {
+        Some(ms) if texts.len() > 1 => {
+            let mut segments = Vec::new();
+            for text in &texts {
+                let mut file_segments = parse_emotion_segments(text);
+                if let Some(last) = file_segments.last_mut() {
+                    last.pause_after_ms = ms;
+                }
+                segments.extend(file_segments);
+            }
+            if let Some(last) = segments.last_mut() {
+                last.pause_after_ms = 0;
+            }
+            segments
+        }
+        _ => parse_emotion_segments(&join_texts(&texts)),
+    };
+    if segments.is_empty() {
+        return Err(ErrorKind::Config.error("no text provided (only emotion tags found)"));
+    }
+    if args.no_join && segments.len() > 1 {
+        return Err(ErrorKind::Config
+            .error("--no-join works on a single run; remove the emotion tags or use one --file"));
     }
 
-    let actual = find_output_file(&out).unwrap_or(out);
-    output::success(&format!("Saved to {}", actual.display()));
+    output::status("Generating", &format!("speech with {voice} voice..."));
+    let runs: Vec<usize> = segments.iter().map(|s| s.text.chars().count()).collect();
+    let timer = stats::Timer::start(&cfg, &runs);
+    let actual = speak_segments(&cfg, &args, &segments, voice, prosody, &out, &post)?.path;
+    timer.finish();
+    let actual = match &append {
+        Some(target) => append_audio(&cfg, target, &actual)?,
+        None => actual,
+    };
 
-    if cfg.auto_play {
-        play_audio(&actual)?;
+    output::success(&saved_message(&actual));
+
+    if cfg.auto_play && actual.is_file() {
+        play_audio(&cfg, &actual)?;
     }
 
     Ok(())
 }
 
+/// Generate `segments` into `out`, joining them when there are several.
+/// Returns the final audio file path.
+fn speak_segments(
+    cfg: &Config,
+    args: &SpeakArgs,
+    segments: &[Segment],
+    voice: &str,
+    prosody: Prosody,
+    out: &Path,
+    post: &PostProcess,
+) -> Result<Committed> {
+    if let [segment] = segments {
+        let emotion = segment.emotion.as_deref().or(args.emotion.as_deref());
+        let instruct = speak_instruct(args.instruct.as_deref(), voice, emotion);
+        return generate_atomic(
+            cfg,
+            &TtsParams {
+                text: &segment.text,
+                instruct: &instruct,
+                speed: prosody.speed,
+                pitch: prosody.pitch,
+                output_path: out,
+                ref_audio: None,
+                ref_text: None,
+                voice: Some(voice),
+                join: !args.no_join,
+            },
+            post,
+        );
+    }
+
+    let mut temp = TempWorkspace::new();
+    let staging = temp.track(staging_path(out));
+    let segments_dir = temp.track(staging.with_extension("segments"));
+    generate_segments(
+        cfg,
+        segments,
+        args.emotion.as_deref(),
+        args.instruct.as_deref(),
+        voice,
+        prosody,
+        &segments_dir,
+    )
+    .and_then(|parts| {
+        // A segment's pause follows its last chunk
+        let gaps: Vec<u32> = parts
+            .iter()
+            .zip(segments)
+            .flat_map(|(chunks, segment)| {
+                let mut gaps = vec![0; chunks.len()];
+                *gaps.last_mut().expect("segments have audio") = segment.pause_after_ms;
+                gaps
+            })
+            .collect();
+        audio::concat_wavs(
+            &parts.concat(),
+            &gaps,
+            post.crossfade_ms,
+            &joined_output_path(&staging)?,
+        )?;
+        commit_output(cfg, &staging, out, post, &mut temp)
+    })
+}
+
+/// One entry of a batch `manifest.json`.
+#[derive(Serialize)]
+struct ManifestItem {
+    index: usize,
+    text: String,
+    output: Option<PathBuf>,
+    voice: String,
+    emotion: Option<String>,
+    instruct: Option<String>,
+    speed: f32,
+    pitch: f32,
+    duration_secs: Option<f64>,
+    /// Recorded with `--analyze --json`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    levels: Option<audio::Levels>,
+    status: &'static str,
+    error: Option<String>,
+}
+
+#[derive(Serialize)]
+struct Manifest {
+    source: PathBuf,
+    items: Vec<ManifestItem>,
+}
+
+/// Read the non-empty lines of a batch file, each normalized.
+fn read_batch(source: &Path, normalize: &normalize::Options) -> Result<Vec<String>> {
+    let lines: Vec<String> = read_text_file(source)?
+        .lines()
+        .map(str::trim)
+        .filter(|l| !l.is_empty())
+        .map(|l| normalize_text(normalize, l))
+        .collect();
+    if lines.is_empty() {
+        return Err(
+            ErrorKind::Config.error(format!("batch file {} has no text lines", source.display()))
+        );
+    }
+    Ok(lines)
+}
+
+/// Output format of a batch: `--format`, then `default_format`, then WAV.
+fn batch_format(cfg: &Config, args: &SpeakArgs) -> Result<String> {
+    let ext = args
+        .format
+        .as_deref()
+        .or(cfg.default_format.as_deref())
+        .unwrap_or("wav")
+        .trim_start_matches('.')
+        .to_lowercase();
+    audio::validate_format(&ext)?;
+    Ok(ext)
+}
+
+/// Report the disk space a batch would take, from each line's expected
+/// length at the requested speed, without generating or creating anything.
+fn estimate_batch(cfg: &Config, args: &SpeakArgs, batch: &str) -> Result<()> {
+    let source = config::expand_path(batch);
+    let lines = read_batch(&source, &args.normalize.with_defaults(cfg))?;
+    let ext = batch_format(cfg, args)?;
+    let post = args.post.with_defaults(cfg);
+    let prosody = Prosody::resolve(cfg, args.speed, args.pitch)?;
+    let rate = post.sample_rate.unwrap_or(audio::NATIVE_SAMPLE_RATE);
+    let pad_secs = f64::from(post.pad_start_ms + post.pad_end_ms) / 1000.0;
+
+    let mut total_secs = 0.0;
+    for line in &lines {
+        let words: f64 = parse_emotion_segments(line)
+            .iter()
+            .map(|s| voices::transcript_words(&s.text))
+            .sum();
+        let mut secs = words / NATURAL_WORDS_PER_SEC / f64::from(prosody.speed);
+        if let Some(max) = post.max_secs.or(cfg.max_output_seconds) {
+            secs = secs.min(f64::from(max));
+        }
+        total_secs += secs + pad_secs;
+    }
+    let bytes = audio::estimated_size(&ext, rate, total_secs);
+    println!(
+        "{} items, about {:.0}s of audio: ~{} as .{ext}",
+        lines.len(),
+        total_secs,
+        models::human_size(bytes)
+    );
+    Ok(())
+}
+
+/// Generate each non-empty line of `batch` into its own numbered file and
+/// write `manifest.json` describing every item next to them. Failed items
+/// are recorded and summarized instead of stopping the run.
+fn speak_batch(cfg: &Config, args: &SpeakArgs, batch: &str) -> Result<()> {
+    if let Some(ext) = args
+        .output
+        .as_deref()
+        .and_then(|p| audio::extension(Path::new(p)))
+    {
+        return Err(ErrorKind::Config.error(format!(
+            "--batch writes a directory of audio files; give --output a directory path \
+             without the .{ext} extension"
+        )));
+    }
+    let source = config::expand_path(batch);
+    let lines = read_batch(&source, &args.normalize.with_defaults(cfg))?;
+
+    // --output names the results directory; otherwise a new one in output_dir
+    let dir = match &args.output {
+        Some(p) => explicit_output(p)?,
+        None => {
+            let now = SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)
+                .unwrap();
+            config::expand_path(&cfg.output_dir).join(format!("batch_{}", now.as_millis()))
+        }
+    };
+    if dir.is_file() {
+        return Err(ErrorKind::Config.error(format!(
+            "--output must be a directory with --batch: {} is a file",
+            dir.display()
+        )));
+    }
+    fs::create_dir_all(&dir).with_context(|| format!("failed to create {}", dir.display()))?;
+    config::ensure_writable(&dir, "output_dir")?;
+
+    let ext = batch_format(cfg, args)?;
+
+    let mut post = args.post.with_defaults(cfg);
+    // One JSON array at the end instead of an object per item
+    let json_levels = post.analyze == Some(LevelReport::Json);
+    if json_levels {
+        post.analyze = Some(LevelReport::Batch);
+    }
+    let voice = args.voice.as_deref().unwrap_or(&cfg.default_voice);
+    warn_voice_typo(cfg, voice);
+    let prosody = Prosody::resolve(cfg, args.speed, args.pitch)?;
+
+    output::status(
+        "Generating",
+        &format!("{} items with {voice} voice...", lines.len()),
+    );
+    let mut report = output::BatchReport::new("items");
+    let mut items = Vec::with_capacity(lines.len());
+    for (i, line) in lines.iter().enumerate() {
+        let index = i + 1;
+        let out = dir.join(format!("{index:03}.{ext}"));
+        let name = out.file_name().unwrap_or_default().to_string_lossy();
+        progress::emit(Event::ItemStarted {
+            index,
+            total: lines.len(),
+            name: &name,
+        });
+
+        let segments = parse_emotion_segments(line);
+        let result = if segments.is_empty() {
+            Err(ErrorKind::Config.error("no text (only emotion tags found)"))
+        } else {
+            speak_segments(cfg, args, &segments, voice, prosody, &out, &post)
+        };
+        let error = result.as_ref().err().map(|e| format!("{e:#}"));
+        let mut levels = None;
+        let result = result.map(|committed| {
+            levels = committed.levels;
+            committed.path
+        });
+        let actual = report.record(&format!("item {index}"), result);
+        progress::emit(Event::ItemFinished {
+            index,
+            total: lines.len(),
+            ok: actual.is_some(),
+        });
+        let duration_secs = actual
+            .as_deref()
+            .filter(|p| audio::extension(p).as_deref() == Some("wav"))
+            .and_then(|p| audio::duration_secs(p).ok());
+
+        items.push(ManifestItem {
+            index,
+            text: line.to_string(),
+            output: actual,
+            voice: voice.to_string(),
+            emotion: args.emotion.clone(),
+            instruct: args.instruct.clone(),
+            speed: prosody.speed,
+            pitch: prosody.pitch,
+            duration_secs,
+            levels: levels.filter(|_| json_levels),
+            status: if error.is_none() { "ok" } else { "failed" },
+            error,
+        });
+    }
+
+    let manifest_path = dir.join("manifest.json");
+    let manifest = Manifest { source, items };
+    let text = serde_json::to_string_pretty(&manifest).context("failed to serialize manifest")?;
+    fs::write(&manifest_path, text + "\n")
+        .with_context(|| format!("failed to write {}", manifest_path.display()))?;
+    output::status("Manifest", &manifest_path.to_string_lossy());
+
+    if json_levels {
+        let levels: Vec<LevelsJson> = manifest
+            .items
+            .iter()
+            .filter_map(|item| {
+                Some(LevelsJson {
+                    output: item.output.as_deref()?,
+                    levels: item.levels?,
+                })
+            })
+            .collect();
+        output::print_json(&levels)?;
+    }
+    report.finish()
+}
+
 pub fn design(args: DesignArgs) -> Result<()> {
+    if args.description.trim().is_empty() {
+        return Err(ErrorKind::Config.error(
+            "voice description is empty; describe the voice, e.g. \"A deep calm British narrator\"",
+        ));
+    }
     let cfg = config::load()?;
     cleanup_old_outputs(&cfg);
-    let text = resolve_text(args.text.as_deref(), args.file.as_deref())?;
-    let out = resolve_output(args.output.as_deref(), &cfg);
-    let speed = args.speed.unwrap_or(cfg.default_speed);
+    let append = args.append.as_deref().map(append_target).transpose()?;
+    // Checked before the editor can open, so typed text isn't lost to a bad path
+    let out = match &append {
+        Some(target) => append_staging(target),
+        None if args.no_join => resolve_chunk_dir(args.output.as_deref(), &cfg)?,
+        None => resolve_output(args.output.as_deref(), args.format.as_deref(), &cfg)?,
+    };
+    let text = resolve_text(
+        args.text.as_deref(),
+        &args.file,
+        "--text <TEXT>",
+        &args.normalize.with_defaults(&cfg),
+    )?;
+    let post = args.post.with_defaults(&cfg);
+    let prosody = Prosody::resolve(&cfg, args.speed, args.pitch)?;
 
-    let instruct = args.description;
+    let instruct = design_instruct(&cfg, &args.description, args.raw);
 
     output::status("Designing", "voice from description...");
 
-    let status = run_tts_command(
+    let timer = stats::Timer::start(&cfg, &[text.chars().count()]);
+    let actual = generate_atomic(
         &cfg,
         &TtsParams {
             text: &text,
             instruct: &instruct,
-            speed,
+            speed: prosody.speed,
+            pitch: prosody.pitch,
             output_path: &out,
             ref_audio: None,
             ref_text: None,
             voice: None,
+            join: !args.no_join,
         },
-    )?;
-
-    if !status.success() {
-        anyhow::bail!("TTS generation failed");
-    }
+        &post,
+    )?
+    .path;
+    timer.finish();
+    let actual = match &append {
+        Some(target) => append_audio(&cfg, target, &actual)?,
+        None => actual,
+    };
 
-    let actual = find_output_file(&out).unwrap_or(out);
-    output::success(&format!("Saved to {}", actual.display()));
+    output::success(&saved_message(&actual));
 
-    if cfg.auto_play {
-        play_audio(&actual)?;
+    if cfg.auto_play && actual.is_file() {
+        play_audio(&cfg, &actual)?;
     }
 
     Ok(())
@@ -232,10 +1283,32 @@ pub fn design(args: DesignArgs) -> Result<()> {
 
 pub fn clone(args: CloneArgs) -> Result<()> {
     let cfg = config::load()?;
-    cleanup_old_outputs(&cfg);
-    let text = resolve_text(args.text.as_deref(), args.file.as_deref())?;
-    let out = resolve_output(args.output.as_deref(), &cfg);
-    let speed = args.speed.unwrap_or(cfg.default_speed);
+    let actual = clone_to_file(&cfg, &args)?;
+
+    if cfg.auto_play && actual.is_file() {
+        play_audio(&cfg, &actual)?;
+    }
+
+    Ok(())
+}
+
+/// Run the clone pipeline and return the path of the saved audio.
+pub(crate) fn clone_to_file(cfg: &Config, args: &CloneArgs) -> Result<PathBuf> {
+    cleanup_old_outputs(cfg);
+
+    // --variant, else the saved voice's preferred variant, else the config's
+    let preferred = args.variant.clone().or_else(|| {
+        let name = args.voice.as_deref()?;
+        let variant = voices::preferred_variant(cfg, name)?;
+        if variant != cfg.model_variant {
+            output::status("Model", &format!("{variant} (preferred by voice '{name}')"));
+        }
+        Some(variant)
+    });
+    let cfg = &Config {
+        model_variant: preferred.unwrap_or_else(|| cfg.model_variant.clone()),
+        ..cfg.clone()
+    };
 
     // Resolve reference audio — either from --ref or --voice (saved voice)
     let (ref_audio, ref_text) = if let Some(voice_name) = &args.voice {
@@ -243,10 +1316,7 @@ pub fn clone(args: CloneArgs) -> Result<()> {
         let wav = voices_dir.join(format!("{voice_name}.wav"));
         let txt = voices_dir.join(format!("{voice_name}.txt"));
         if !wav.exists() {
-            anyhow::bail!(
-                "voice '{voice_name}' not found (no {}.wav in voices dir)",
-                voice_name
-            );
+            return Err(voices::not_found(cfg, voice_name));
         }
         let transcript = if txt.exists() {
             Some(fs::read_to_string(&txt).context("failed to read voice transcript")?)
@@ -254,77 +1324,819 @@ pub fn clone(args: CloneArgs) -> Result<()> {
             args.ref_text.clone()
         };
         (wav.to_string_lossy().to_string(), transcript)
-    } else if let Some(ref_path) = &args.ref_audio {
-        (ref_path.clone(), args.ref_text.clone())
+    } else if !args.ref_audio.is_empty() {
+        let clips = reference_clips(&args.ref_audio)?;
+        if let [clip] = clips.as_slice() {
+            let transcript = args.ref_text.clone().or_else(|| {
+                if args.auto_transcript {
+                    return None;
+                }
+                let text = audio::embedded_transcript(clip)?;
+                output::status("Transcript", "read from the reference WAV's metadata");
+                Some(text)
+            });
+            (clip.to_string_lossy().to_string(), transcript)
+        } else {
+            // The backends take one reference, so several become one clip
+            let mut temp = TempWorkspace::new();
+            let merged = temp.track(
+                config::base_dir()
+                    .join("tmp")
+                    .join(format!("refs-{}.wav", std::process::id())),
+            );
+            fs::create_dir_all(merged.parent().unwrap())?;
+            output::status(
+                "Merging",
+                &format!("{} reference clips into one...", clips.len()),
+            );
+            audio::merge_references(&clips, &merged)?;
+            return clone_from_reference(
+                cfg,
+                args,
+                &merged.to_string_lossy(),
+                args.ref_text.clone(),
+            );
+        }
     } else {
-        anyhow::bail!("provide either --ref <audio_file> or --voice <saved_voice>");
+        return Err(
+            ErrorKind::Config.error("provide either --ref <audio_file> or --voice <saved_voice>")
+        );
+    };
+
+    // Some backends fail on 24-bit or float WAVs, so they get a 16-bit copy
+    let mut temp = TempWorkspace::new();
+    let ref_audio = match audio::non_pcm16_format(Path::new(&ref_audio)) {
+        Some(format) => {
+            let converted = temp.track(
+                config::base_dir()
+                    .join("tmp")
+                    .join(format!("ref-{}.wav", std::process::id())),
+            );
+            fs::create_dir_all(converted.parent().unwrap())?;
+            output::status("Converting", &format!("{format} reference to 16-bit PCM"));
+            audio::to_pcm16(Path::new(&ref_audio), &converted)?;
+            converted.to_string_lossy().to_string()
+        }
+        None => ref_audio,
+    };
+    clone_from_reference(cfg, args, &ref_audio, ref_text)
+}
+
+/// Check that every `--ref` clip exists.
+pub(crate) fn reference_clips(paths: &[String]) -> Result<Vec<PathBuf>> {
+    paths
+        .iter()
+        .map(|p| {
+            let path = config::expand_path(p);
+            if path.is_file() {
+                Ok(path)
+            } else {
+                Err(ErrorKind::Config.error(format!("reference audio not found: {p}")))
+            }
+        })
+        .collect()
+}
+
+/// The clone pipeline after the reference audio is settled.
+fn clone_from_reference(
+    cfg: &Config,
+    args: &CloneArgs,
+    ref_audio: &str,
+    ref_text: Option<String>,
+) -> Result<PathBuf> {
+    let append = args.append.as_deref().map(append_target).transpose()?;
+    // Checked before the editor can open, so typed text isn't lost to a bad path
+    let out = match &append {
+        Some(target) => append_staging(target),
+        None if args.no_join => resolve_chunk_dir(args.output.as_deref(), cfg)?,
+        None => resolve_output(args.output.as_deref(), args.format.as_deref(), cfg)?,
+    };
+    let text = resolve_text(
+        args.text.as_deref(),
+        &args.file,
+        "--text <TEXT>",
+        &args.normalize.with_defaults(cfg),
+    )?;
+    let post = args.post.with_defaults(cfg);
+    let prosody = Prosody::resolve(cfg, args.speed, args.pitch)?;
+
+    // Some models need the reference transcript; don't send None silently
+    let ref_text = match ref_text {
+        Some(t) if !t.trim().is_empty() => Some(t),
+        _ if args.auto_transcript => Some(voices::transcribe(cfg, Path::new(ref_audio))?),
+        _ => {
+            output::warn(
+                "no transcript for the reference audio; cloning quality may suffer.\n\
+                 Pass --ref-text, use --auto-transcript, or save one with `qwen-tts voices add --transcript`",
+            );
+            None
+        }
     };
 
     output::status("Cloning", "voice from reference audio...");
 
-    let status = run_tts_command(
-        &cfg,
+    let timer = stats::Timer::start(cfg, &[text.chars().count()]);
+    let actual = generate_atomic(
+        cfg,
         &TtsParams {
             text: &text,
             instruct: "Clone the voice from the reference audio.",
-            speed,
+            speed: prosody.speed,
+            pitch: prosody.pitch,
             output_path: &out,
-            ref_audio: Some(&ref_audio),
+            ref_audio: Some(ref_audio),
             ref_text: ref_text.as_deref(),
             voice: None,
+            join: !args.no_join,
         },
-    )?;
+        &post,
+    )?
+    .path;
+    timer.finish();
+    let actual = match &append {
+        Some(target) => append_audio(cfg, target, &actual)?,
+        None => actual,
+    };
+
+    output::success(&saved_message(&actual));
+    Ok(actual)
+}
+
+/// Accepted `--speed` multipliers.
+pub const SPEED_RANGE: std::ops::RangeInclusive<f32> = 0.25..=4.0;
+/// Accepted `--pitch` shifts, in semitones.
+pub const PITCH_RANGE: std::ops::RangeInclusive<f32> = -12.0..=12.0;
+
+/// Speaking rate and pitch for one command.
+#[derive(Debug, Clone, Copy)]
+struct Prosody {
+    speed: f32,
+    pitch: f32,
+}
 
-    if !status.success() {
-        anyhow::bail!("TTS generation failed");
+impl Prosody {
+    /// Validate the flags (falling back to `default_speed`) and drop a pitch
+    /// shift the backend would ignore, with a warning.
+    fn resolve(cfg: &Config, speed: Option<f32>, pitch: Option<f32>) -> Result<Self> {
+        let speed = speed.unwrap_or(cfg.default_speed);
+        if !SPEED_RANGE.contains(&speed) {
+            return Err(ErrorKind::Config.error(format!(
+                "speed must be between {} and {}, got {speed}",
+                SPEED_RANGE.start(),
+                SPEED_RANGE.end()
+            )));
+        }
+        let mut pitch = pitch.unwrap_or(0.0);
+        if !PITCH_RANGE.contains(&pitch) {
+            return Err(ErrorKind::Config.error(format!(
+                "pitch must be between {} and {} semitones, got {pitch}",
+                PITCH_RANGE.start(),
+                PITCH_RANGE.end()
+            )));
+        }
+        if pitch != 0.0 && !supports_pitch(cfg) {
+            output::warn(&if cfg.backend == Backend::Custom {
+                "--pitch is ignored: custom_command has no {pitch} placeholder".to_string()
+            } else {
+                format!(
+                    "--pitch is ignored: the {} backend's models have no pitch control.\n\
+                     Use the custom backend with a {{pitch}} placeholder in custom_command to shift pitch.",
+                    cfg.backend
+                )
+            });
+            pitch = 0.0;
+        }
+        Ok(Self { speed, pitch })
     }
+}
 
-    let actual = find_output_file(&out).unwrap_or(out);
-    output::success(&format!("Saved to {}", actual.display()));
+/// Qwen3-TTS has no pitch input, so only a custom command that asks for
+/// `{pitch}` can honor it.
+fn supports_pitch(cfg: &Config) -> bool {
+    cfg.backend == Backend::Custom
+        && cfg
+            .custom_command
+            .as_deref()
+            .is_some_and(|template| template.contains("{pitch}"))
+}
 
-    if cfg.auto_play {
-        play_audio(&actual)?;
+#[derive(Clone, Copy)]
+pub(crate) struct TtsParams<'a> {
+    pub text: &'a str,
+    pub instruct: &'a str,
+    pub speed: f32,
+    /// Semitones; only forwarded to backends that support it
+    pub pitch: f32,
+    pub output_path: &'a Path,
+    pub ref_audio: Option<&'a str>,
+    pub ref_text: Option<&'a str>,
+    pub voice: Option<&'a str>,
+    /// Ask the backend for one joined file rather than a file per chunk
+    pub join: bool,
+}
+
+/// Staging path next to `out` that generation writes into, so a crash never
+/// leaves a partial file at the destination.
+fn staging_path(out: &Path) -> PathBuf {
+    let stem = out
+        .file_stem()
+        .map(|s| s.to_string_lossy().to_string())
+        .unwrap_or_default();
+    let pid = std::process::id();
+    // Formats that need conversion are generated as WAV first
+    let name = match out.extension() {
+        Some(_) if audio::needs_transcode(out) => format!(".{stem}.partial-{pid}.wav"),
+        Some(ext) => format!(".{stem}.partial-{pid}.{}", ext.to_string_lossy()),
+        None => format!(".{stem}.partial-{pid}"),
+    };
+    out.with_file_name(name)
+}
+
+fn remove_path(path: &Path) {
+    if path.is_dir() {
+        fs::remove_dir_all(path).ok();
+    } else if path.exists() {
+        fs::remove_file(path).ok();
     }
+}
 
-    Ok(())
+static KEEP_TEMP: OnceLock<()> = OnceLock::new();
+
+/// Leave scratch files in place for this process (the global `--keep-temp` flag).
+pub fn set_keep_temp() {
+    let _ = KEEP_TEMP.set(());
 }
 
-struct TtsParams<'a> {
-    text: &'a str,
-    instruct: &'a str,
-    speed: f32,
-    output_path: &'a Path,
-    ref_audio: Option<&'a str>,
-    ref_text: Option<&'a str>,
-    voice: Option<&'a str>,
+/// Scratch paths of one run: staging files, segment directories, resampled
+/// and merged WAVs. Whatever is still on disk when the workspace is dropped
+/// is removed, whether the run succeeded, failed, or panicked; paths that
+/// were renamed into place are already gone. With `--keep-temp` they are
+/// listed and left for debugging instead.
+pub(crate) struct TempWorkspace {
+    paths: Vec<PathBuf>,
 }
 
-fn run_tts_command(cfg: &Config, params: &TtsParams) -> Result<std::process::ExitStatus> {
-    let python = config::expand_path(&cfg.python_path);
-    let model = model_id(cfg)?;
+impl TempWorkspace {
+    pub(crate) fn new() -> Self {
+        Self { paths: Vec::new() }
+    }
+
+    /// Register `path` for cleanup and hand it back.
+    pub(crate) fn track(&mut self, path: PathBuf) -> PathBuf {
+        self.paths.push(path.clone());
+        path
+    }
+}
+
+impl Drop for TempWorkspace {
+    fn drop(&mut self) {
+        // Newest first, so files inside a tracked directory go before it
+        for path in self.paths.iter().rev() {
+            if KEEP_TEMP.get().is_none() {
+                remove_path(path);
+            } else if path.exists() {
+                output::status("Kept", &path.display().to_string());
+            }
+        }
+    }
+}
+
+/// Post-generation processing applied to the WAV before it is moved into place.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct PostProcess {
+    pub pad_start_ms: u32,
+    pub pad_end_ms: u32,
+    /// Cut the generated speech to this many seconds (before padding)
+    pub max_secs: Option<f32>,
+    /// Resample the output to this rate (falls back to `default_sample_rate`)
+    pub sample_rate: Option<u32>,
+    /// Report the finished audio's levels (`--analyze`)
+    pub analyze: Option<LevelReport>,
+    /// Overlap adjacent segments and backend chunks by this many
+    /// milliseconds when joining them
+    pub crossfade_ms: u32,
+}
+
+/// How `--analyze` reports levels: a status line, or JSON on stdout.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LevelReport {
+    Text,
+    Json,
+    /// Measured without printing, for a batch that prints all items'
+    /// levels as one JSON array
+    Batch,
+}
+
+/// Levels of one output, as printed by `--analyze --json`.
+#[derive(Serialize)]
+struct LevelsJson<'a> {
+    output: &'a Path,
+    #[serde(flatten)]
+    levels: audio::Levels,
+}
+
+/// Measure and print the levels of `wav`, which becomes `out`.
+fn report_levels(wav: &Path, out: &Path, report: LevelReport) -> Result<audio::Levels> {
+    let levels = audio::levels(wav)?;
+    match report {
+        LevelReport::Json => {
+            output::print_json(&LevelsJson {
+                output: out,
+                levels,
+            })?;
+            return Ok(levels);
+        }
+        LevelReport::Batch => return Ok(levels),
+        LevelReport::Text => {}
+    }
+    let db = |level: Option<f64>| level.map_or("silent".to_string(), |db| format!("{db:.1} dBFS"));
+    let clipping = match levels.clipped_samples {
+        0 => "no clipping".to_string(),
+        n => format!(
+            "{n} clipped sample{} ({:.2}%)",
+            if n == 1 { "" } else { "s" },
+            n as f64 * 100.0 / levels.samples as f64
+        ),
+    };
+    output::status(
+        "Levels",
+        &format!(
+            "peak {}, RMS {}, {clipping}",
+            db(levels.peak_dbfs),
+            db(levels.rms_dbfs)
+        ),
+    );
+    Ok(levels)
+}
+
+impl PostProcess {
+    /// Fill unset options from the config defaults.
+    fn with_defaults(self, cfg: &Config) -> Self {
+        Self {
+            sample_rate: self.sample_rate.or(cfg.default_sample_rate),
+            ..self
+        }
+    }
+
+    fn apply(&self, wav: &Path, temp: &mut TempWorkspace) -> Result<()> {
+        if let Some(rate) = self.sample_rate {
+            if audio::sample_rate(wav)? != rate {
+                let resampled = temp.track(wav.with_extension("resampled.wav"));
+                audio::resample(wav, &resampled, rate)?;
+                fs::rename(&resampled, wav)
+                    .with_context(|| format!("failed to replace {}", wav.display()))?;
+            }
+        }
+        if let Some(secs) = self.max_secs {
+            audio::truncate(wav, secs)?;
+        }
+        if self.pad_start_ms > 0 || self.pad_end_ms > 0 {
+            audio::pad_silence(wav, self.pad_start_ms, self.pad_end_ms)?;
+        }
+        Ok(())
+    }
+}
+
+/// A generation moved into place.
+struct Committed {
+    /// The final audio file, or the `--no-join` chunk directory
+    path: PathBuf,
+    /// Levels measured with `--analyze`
+    levels: Option<audio::Levels>,
+}
+
+/// Move a completed generation from `staging` to `out` with a rename,
+/// converting it first when `out` asks for a non-WAV format, and apply the
+/// configured output permissions.
+fn commit_output(
+    cfg: &Config,
+    staging: &Path,
+    out: &Path,
+    post: &PostProcess,
+    temp: &mut TempWorkspace,
+) -> Result<Committed> {
+    let produced = join_chunks(staging, post.crossfade_ms)?;
+    post.apply(&produced, temp)?;
+    let levels = post
+        .analyze
+        .map(|report| report_levels(&produced, out, report))
+        .transpose()?;
+
+    if out.extension().is_some() {
+        let finished = if audio::needs_transcode(out) {
+            let converted = temp.track(staging.with_extension(out.extension().unwrap()));
+            output::status("Converting", &format!("to {}...", out.display()));
+            audio::transcode(&produced, &converted)?;
+            converted
+        } else {
+            produced
+        };
+        fs::rename(&finished, out)
+            .with_context(|| format!("failed to move output to {}", out.display()))?;
+    } else {
+        fs::rename(staging, out)
+            .with_context(|| format!("failed to move output to {}", out.display()))?;
+    }
 
+    config::apply_output_mode(cfg, out)?;
+    Ok(Committed {
+        path: find_output_file(out).unwrap_or_else(|| out.to_path_buf()),
+        levels,
+    })
+}
+
+/// Move a `--no-join` chunk directory from `staging` to `out` as it is.
+fn commit_chunks(cfg: &Config, staging: &Path, out: &Path) -> Result<Committed> {
+    if !staging.is_dir() {
+        return Err(ErrorKind::Backend.error(
+            "the backend wrote a single file, not a directory of chunks; \
+             --no-join needs the mlx backend, which splits long text into chunks",
+        ));
+    }
+    fs::rename(staging, out)
+        .with_context(|| format!("failed to move output to {}", out.display()))?;
+    config::apply_output_mode(cfg, out)?;
+    Ok(Committed {
+        path: out.to_path_buf(),
+        levels: None,
+    })
+}
+
+/// "Saved to …" for a file, or the chunk count for a `--no-join` directory.
+fn saved_message(path: &Path) -> String {
+    if path.is_dir() {
+        let chunks = chunk_wavs(path).len();
+        format!("Saved {chunks} audio chunk(s) to {}", path.display())
+    } else {
+        format!("Saved to {}", path.display())
+    }
+}
+
+/// `--output` for `--no-join`: a directory for the chunk files, so it takes
+/// no extension and ignores `default_format`. Checked before generation,
+/// along with the backend, which must be one that writes chunks.
+fn resolve_chunk_dir(output: Option<&str>, cfg: &Config) -> Result<PathBuf> {
+    if cfg.backend != Backend::Mlx {
+        return Err(ErrorKind::Config.error(format!(
+            "--no-join needs the mlx backend, which splits long text into chunks; \
+             the {} backend always writes a single file",
+            cfg.backend
+        )));
+    }
+    if let Some(ext) = output.and_then(|p| audio::extension(Path::new(p))) {
+        return Err(ErrorKind::Config.error(format!(
+            "--no-join writes a directory of chunk files; give --output a directory path \
+             without the .{ext} extension"
+        )));
+    }
+    let cfg = Config {
+        default_format: None,
+        ..cfg.clone()
+    };
+    resolve_output(output, None, &cfg)
+}
+
+/// Generate into a staging path and move the result to `params.output_path`
+/// only after the backend finished successfully.
+fn generate_atomic(cfg: &Config, params: &TtsParams, post: &PostProcess) -> Result<Committed> {
+    let mut temp = TempWorkspace::new();
+    let staging = temp.track(staging_path(params.output_path));
+    run_tts_command(
+        cfg,
+        &TtsParams {
+            output_path: &staging,
+            ..*params
+        },
+    )
+    .and_then(|()| {
+        if params.join {
+            commit_output(cfg, &staging, params.output_path, post, &mut temp)
+        } else {
+            commit_chunks(cfg, &staging, params.output_path)
+        }
+    })
+}
+
+/// Run the backend for one generation. A non-zero exit becomes an error
+/// whose message explains common Python failures.
+pub(crate) fn run_tts_command(cfg: &Config, params: &TtsParams) -> Result<()> {
     // Ensure output directory exists
     if let Some(parent) = params.output_path.parent() {
         fs::create_dir_all(parent)?;
     }
 
-    let mut cmd = Command::new(python.to_string_lossy().as_ref());
+    // The exact prompt, for debugging voice/emotion/--instruct behavior
+    output::verbose("Instruct", params.instruct);
+
+    progress::emit(Event::GenerationStarted {
+        output: params.output_path,
+    });
+    let start = Instant::now();
+    let result = run_backend(cfg, params);
+    if result.is_ok() {
+        stats::record(
+            cfg,
+            &usage_voice(cfg, params),
+            params.text.chars().count(),
+            start.elapsed(),
+        );
+    }
+    progress::emit(Event::GenerationFinished {
+        output: params.output_path,
+        ok: result.is_ok(),
+    });
+    result
+}
 
-    match cfg.backend {
-        Backend::Mlx => {
-            cmd.args(["-m", "mlx_audio.tts.generate"]);
+/// Voice a run is counted under in the usage statistics: the preset voice,
+/// the saved voice a clone came from, or the kind of run otherwise.
+fn usage_voice(cfg: &Config, params: &TtsParams) -> String {
+    if let Some(voice) = params.voice {
+        return voice.to_string();
+    }
+    let Some(reference) = params.ref_audio.map(Path::new) else {
+        return "(designed)".to_string();
+    };
+    let voices_dir = config::expand_path(&cfg.voices_dir);
+    match reference.file_stem() {
+        Some(name) if reference.parent() == Some(voices_dir.as_path()) => {
+            name.to_string_lossy().to_string()
         }
-        Backend::Cuda | Backend::Cpu => {
-            let script = config::base_dir().join("generate_compat.py");
-            cmd.arg(script.to_string_lossy().as_ref());
+        _ => "(cloned)".to_string(),
+    }
+}
+
+fn run_backend(cfg: &Config, params: &TtsParams) -> Result<()> {
+    if let Some(result) = warm::generate(cfg, params) {
+        return result.and_then(|()| check_output(cfg, params));
+    }
+    let mut cmd = match cfg.backend {
+        Backend::Custom => custom_command(cfg, params)?,
+        _ => python_command(cfg, params)?,
+    };
+    // The backend downloads the model itself when it isn't installed yet
+    cmd.envs(cfg.network_env()).envs(cfg.hf_auth_env());
+
+    let log = if cfg.quiet_backend {
+        Some(open_backend_log(&cmd)?)
+    } else {
+        None
+    };
+    let stdout = match &log {
+        Some(file) => std::process::Stdio::from(file.try_clone()?),
+        None => std::process::Stdio::inherit(),
+    };
+    let mut child = cmd
+        .stdout(stdout)
+        .stderr(std::process::Stdio::piped())
+        .spawn()
+        .map_err(|e| {
+            ErrorKind::Backend.error(format!(
+                "failed to run TTS command {}: {e}",
+                cmd.get_program().to_string_lossy()
+            ))
+        })?;
+
+    let stderr = child.stderr.take().map(|pipe| tee_stderr(pipe, log));
+    let status = wait_backend(cfg, &mut child);
+    let captured = stderr
+        .and_then(|handle| handle.join().ok())
+        .unwrap_or_default();
+
+    if matches!(status, Ok(s) if s.success()) {
+        return check_output(cfg, params);
+    }
+    let captured = String::from_utf8_lossy(&captured);
+    if let Err(e) = status {
+        // A timeout or wait error; its output still goes into last_error.log
+        output::record_backend_stderr(&captured);
+        return Err(e);
+    }
+    Err(backend_failure(cfg, &captured))
+}
+
+/// The error for a failed backend run, diagnosed from its `stderr`, which
+/// `save_last_error` also keeps.
+pub(crate) fn backend_failure(cfg: &Config, stderr: &str) -> anyhow::Error {
+    output::record_backend_stderr(stderr);
+    ErrorKind::Backend.error(diagnose_backend_error(cfg, stderr))
+}
+
+/// Checks on a successful backend run's output: warn about suspicious
+/// lengths and cut anything over `max_output_seconds`.
+fn check_output(cfg: &Config, params: &TtsParams) -> Result<()> {
+    check_output_length(cfg, params);
+    let Some(limit) = cfg.max_output_seconds else {
+        return Ok(());
+    };
+    if !params.join {
+        return Ok(());
+    }
+    let Some(wav) = find_output_file(params.output_path)
+        .filter(|p| audio::extension(p).as_deref() == Some("wav"))
+    else {
+        return Ok(());
+    };
+    let secs = audio::duration_secs(&wav)?;
+    if secs > f64::from(limit) {
+        output::warn(&format!(
+            "the backend produced {secs:.1}s of audio, more than max_output_seconds \
+             ({limit}s); keeping only the first {limit}s"
+        ));
+        audio::truncate(&wav, limit)?;
+    }
+    Ok(())
+}
+
+/// Texts shorter than this many words can legitimately produce very short
+/// audio, so they are not checked.
+const MIN_CHECKED_WORDS: f64 = 5.0;
+
+/// Typical speaking rate at speed 1.0, the baseline for `verify_speed`.
+const NATURAL_WORDS_PER_SEC: f64 = 2.5;
+
+/// Texts need this many words for their length to reveal the speed.
+const MIN_SPEED_CHECK_WORDS: f64 = 15.0;
+
+/// Requested speeds this close to 1.0 can't be told apart from natural
+/// variation in speaking rate.
+const SPEED_CHECK_NEUTRAL: std::ops::RangeInclusive<f32> = 0.8..=1.25;
+
+/// Warn when the backend succeeded but produced far less audio than even
+/// the fastest plausible reading of the text, a sign of a silent failure.
+/// With `verify_speed`, also warn when the length matches a reading at
+/// normal speed better than one at the requested speed.
+fn check_output_length(cfg: &Config, params: &TtsParams) {
+    if !params.join {
+        return;
+    }
+    let words = voices::transcript_words(params.text);
+    if words < MIN_CHECKED_WORDS {
+        return;
+    }
+    let Some(secs) = find_output_file(params.output_path)
+        .filter(|p| audio::extension(p).as_deref() == Some("wav"))
+        .and_then(|p| audio::duration_secs(&p).ok())
+    else {
+        return;
+    };
+    let fastest = words / (voices::PLAUSIBLE_WORDS_PER_SEC.end() * f64::from(params.speed));
+    if secs < fastest {
+        output::warn(&format!(
+            "the backend produced only {secs:.1}s of audio for about {words:.0} words \
+             (expected at least {fastest:.1}s); it may have failed silently. \
+             Listen to the result, and run with --verbose to see the backend output if it is cut off"
+        ));
+        return;
+    }
+    if cfg.verify_speed {
+        check_speed(cfg, params.speed, words, secs);
+    }
+}
+
+/// Compare the speed implied by the output length with the requested one.
+fn check_speed(cfg: &Config, speed: f32, words: f64, secs: f64) {
+    if SPEED_CHECK_NEUTRAL.contains(&speed) || words < MIN_SPEED_CHECK_WORDS || secs <= 0.0 {
+        return;
+    }
+    let implied = words / NATURAL_WORDS_PER_SEC / secs;
+    output::verbose(
+        "Speed",
+        &format!("requested {speed}, output length suggests about {implied:.2}"),
+    );
+    // Closer to normal speed than to the requested one, on a log scale
+    if (implied / f64::from(speed)).ln().abs() > implied.ln().abs() {
+        output::warn(&format!(
+            "--speed {speed} may have been ignored: {secs:.1}s of audio for about {words:.0} words \
+             sounds like normal speed (about {implied:.2}); the {} backend may not support speed \
+             control for the {} model",
+            cfg.backend, cfg.model_variant
+        ));
+    }
+}
+
+/// Placeholders substituted into `custom_command`.
+pub const CUSTOM_PLACEHOLDERS: &[&str] = &[
+    "{text}",
+    "{instruct}",
+    "{voice}",
+    "{ref_audio}",
+    "{ref_text}",
+    "{speed}",
+    "{pitch}",
+    "{output}",
+    "{model}",
+];
+
+/// Build the `custom` backend's command from the `custom_command` template.
+/// Each argument is substituted separately and no shell is involved, so
+/// text with spaces or quotes stays a single argument.
+fn custom_command(cfg: &Config, params: &TtsParams) -> Result<Command> {
+    let template = cfg.custom_command.as_deref().ok_or_else(|| {
+        ErrorKind::Config.error(
+            "the custom backend needs a command template\n\
+             Set it with `qwen-tts config set custom_command \"my-tts --text {text} --out {output}\"`",
+        )
+    })?;
+    let args = platform::split_command(template)?;
+    let Some((program, rest)) = args.split_first() else {
+        return Err(ErrorKind::Config.error("custom_command is empty"));
+    };
+
+    // Paths without an extension use the `--join_audio` layout: a directory
+    // holding audio.wav
+    let output = if audio::extension(params.output_path).as_deref() == Some("wav") {
+        params.output_path.to_path_buf()
+    } else {
+        fs::create_dir_all(params.output_path)?;
+        params.output_path.join("audio.wav")
+    };
+    let local = config::expand_path(&cfg.models_dir).join(&cfg.model_variant);
+    let model = if models::is_model_installed(cfg, &cfg.model_variant) {
+        local.to_string_lossy().to_string()
+    } else {
+        models::repo_id(cfg.backend, &cfg.model_variant)
+            .unwrap_or_default()
+            .to_string()
+    };
+    let values = [
+        params.text.to_string(),
+        params.instruct.to_string(),
+        params.voice.unwrap_or_default().to_string(),
+        params.ref_audio.unwrap_or_default().to_string(),
+        params.ref_text.unwrap_or_default().to_string(),
+        params.speed.to_string(),
+        params.pitch.to_string(),
+        output.to_string_lossy().to_string(),
+        model,
+    ];
+    let substitute = |arg: &str| fill_placeholders(arg, &values);
+
+    let mut cmd = Command::new(substitute(program));
+    cmd.args(rest.iter().map(|arg| substitute(arg)));
+    Ok(cmd)
+}
+
+/// Replace each placeholder in `arg` with its value in one left-to-right
+/// pass, so placeholders inside the inserted text are left alone.
+fn fill_placeholders(arg: &str, values: &[String]) -> String {
+    let mut filled = String::with_capacity(arg.len());
+    let mut rest = arg;
+    while let Some(start) = rest.find('{') {
+        filled.push_str(&rest[..start]);
+        rest = &rest[start..];
+        let found = CUSTOM_PLACEHOLDERS
+            .iter()
+            .zip(values)
+            .find(|(key, _)| rest.starts_with(**key));
+        match found {
+            Some((key, value)) => {
+                filled.push_str(value);
+                rest = &rest[key.len()..];
+            }
+            None => {
+                filled.push('{');
+                rest = &rest[1..];
+            }
         }
     }
+    filled.push_str(rest);
+    filled
+}
+
+/// The CUDA/CPU generation script, installing the bundled one if needed.
+pub(crate) fn compat_script(cfg: &Config) -> Result<PathBuf> {
+    if cfg.compat_script.is_none() {
+        config::install_compat_script()?;
+    }
+    let script = cfg.compat_script_path();
+    if !script.is_file() {
+        return Err(ErrorKind::Config.error(format!(
+            "generation script not found: {}\n\
+             Set it with `qwen-tts config set compat_script <path>`",
+            script.display()
+        )));
+    }
+    Ok(script)
+}
+
+/// Build the mlx_audio / compat-script command for the Python backends.
+fn python_command(cfg: &Config, params: &TtsParams) -> Result<Command> {
+    let python = config::expand_path(&cfg.python_path);
+    let model = model_id(cfg)?;
+    let mut cmd = Command::new(&python);
+
+    if cfg.backend == Backend::Mlx {
+        cmd.args(["-m", "mlx_audio.tts.generate"]);
+    } else {
+        cmd.arg(compat_script(cfg)?);
+    }
 
     cmd.args(["--model", &model]);
     cmd.args(["--text", params.text]);
     cmd.args(["--instruct", params.instruct]);
     cmd.args(["--speed", &params.speed.to_string()]);
-    cmd.args(["--output_path", &params.output_path.to_string_lossy()]);
+    cmd.arg("--output_path").arg(params.output_path);
 
     // Use --voice to enforce consistent voice across all chunks
     if let Some(voice) = params.voice {
@@ -332,7 +2144,9 @@ fn run_tts_command(cfg: &Config, params: &TtsParams) -> Result<std::process::Exi
     }
 
     // Join all audio chunks into a single file instead of a directory of fragments
-    cmd.arg("--join_audio");
+    if params.join {
+        cmd.arg("--join_audio");
+    }
 
     if let Some(ref_audio) = params.ref_audio {
         cmd.args(["--ref_audio", ref_audio]);
@@ -340,68 +2154,295 @@ fn run_tts_command(cfg: &Config, params: &TtsParams) -> Result<std::process::Exi
     if let Some(ref_text) = params.ref_text {
         cmd.args(["--ref_text", ref_text]);
     }
+    Ok(cmd)
+}
+
+/// How much of the backend's stderr is kept for diagnosing failures.
+pub(crate) const STDERR_TAIL_BYTES: usize = 16 * 1024;
+
+/// Forward the backend's stderr to ours as it arrives, keeping the tail.
+fn tee_stderr(
+    mut pipe: std::process::ChildStderr,
+    mut log: Option<fs::File>,
+) -> std::thread::JoinHandle<Vec<u8>> {
+    use std::io::{Read, Write};
 
-    cmd.stdout(std::process::Stdio::inherit())
-        .stderr(std::process::Stdio::inherit())
-        .status()
-        .context("failed to run TTS command")
+    std::thread::spawn(move || {
+        let mut tail = Vec::new();
+        let mut buf = [0u8; 4096];
+        while let Ok(n) = pipe.read(&mut buf) {
+            if n == 0 {
+                break;
+            }
+            match &mut log {
+                Some(file) => {
+                    file.write_all(&buf[..n]).ok();
+                }
+                None => {
+                    let mut err = std::io::stderr().lock();
+                    err.write_all(&buf[..n]).ok();
+                    err.flush().ok();
+                }
+            }
+            tail.extend_from_slice(&buf[..n]);
+            if tail.len() > STDERR_TAIL_BYTES {
+                tail.drain(..tail.len() - STDERR_TAIL_BYTES);
+            }
+        }
+        tail
+    })
 }
 
-fn play_audio(path: &Path) -> Result<()> {
-    // mlx_audio may create a directory of chunks instead of a single file
-    let files = if path.is_dir() {
-        let mut wavs: Vec<_> = fs::read_dir(path)?
-            .filter_map(|e| e.ok())
-            .map(|e| e.path())
-            .filter(|p| p.extension().and_then(|e| e.to_str()) == Some("wav"))
-            .collect();
-        wavs.sort();
-        wavs
+/// Open `backend.log` for a `quiet_backend` run and start its section. The
+/// first run of a command replaces the log; later ones (segments, batch
+/// items, parallel jobs) append to it.
+fn open_backend_log(cmd: &Command) -> Result<fs::File> {
+    use std::io::Write;
+
+    static REPLACED: OnceLock<()> = OnceLock::new();
+    let path = config::backend_log_path();
+    REPLACED.get_or_init(|| {
+        fs::write(&path, "").ok();
+    });
+    let mut file = fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(&path)
+        .with_context(|| format!("failed to open {}", path.display()))?;
+    writeln!(
+        file,
+        "--- {} {}",
+        output::timestamp(),
+        cmd.get_program().to_string_lossy()
+    )?;
+    output::verbose("Backend", &format!("output goes to {}", path.display()));
+    Ok(file)
+}
+
+/// Turn a failed backend's stderr into a short, actionable message. Unknown
+/// failures fall back to the last lines of output.
+fn diagnose_backend_error(cfg: &Config, stderr: &str) -> String {
+    let python = config::expand_path(&cfg.python_path);
+
+    if stderr.contains("CUDA out of memory")
+        || stderr.contains("OutOfMemoryError")
+        || stderr.contains("MPS backend out of memory")
+        || stderr.contains("Insufficient Memory")
+    {
+        let hint = match models::quantized_variant(cfg.backend, &cfg.model_variant) {
+            Some(quantized) => {
+                format!("try a 4-bit variant (`qwen-tts config set model_variant {quantized}`)")
+            }
+            None => "try the cpu backend (`--backend cpu`) or close other GPU programs".to_string(),
+        };
+        return format!("TTS generation failed: out of GPU memory — {hint}");
+    }
+
+    if let Some(module) = stderr
+        .lines()
+        .rev()
+        .find_map(|l| l.split("No module named ").nth(1))
+    {
+        let module = module.trim().trim_matches('\'');
+        let root = module.split('.').next().unwrap_or(module);
+        return format!(
+            "TTS generation failed: Python module '{root}' is not installed in {}\n\
+             Install it with `{} -m pip install {}`",
+            python.display(),
+            python.display(),
+            root.replace('_', "-")
+        );
+    }
+
+    if stderr.contains("Torch not compiled with CUDA enabled") {
+        return "TTS generation failed: the installed PyTorch has no CUDA support\n\
+                Install a CUDA build of torch, or use `--backend cpu`"
+            .to_string();
+    }
+
+    let model_missing = [
+        "config.json",
+        ".safetensors",
+        "does not appear to have a file named",
+    ];
+    if (stderr.contains("No such file or directory")
+        || stderr.contains("does not appear to have a file named")
+        || stderr.contains("Can't load"))
+        && model_missing.iter().any(|s| stderr.contains(s))
+    {
+        return format!(
+            "TTS generation failed: model files for '{}' are missing or incomplete\n\
+             Re-download them with `qwen-tts models update --variant {}`",
+            cfg.model_variant, cfg.model_variant
+        );
+    }
+
+    let lines: Vec<&str> = stderr
+        .lines()
+        .map(str::trim_end)
+        .filter(|l| !l.is_empty())
+        .collect();
+    let tail = &lines[lines.len().saturating_sub(5)..];
+    if tail.is_empty() {
+        "TTS generation failed".to_string()
     } else {
-        vec![path.to_path_buf()]
+        format!("TTS generation failed:\n{}", tail.join("\n"))
+    }
+}
+
+/// Wait for the backend, stopping it after the configured timeout.
+fn wait_backend(cfg: &Config, child: &mut std::process::Child) -> Result<std::process::ExitStatus> {
+    let Some(limit) = cfg.generation_timeout.filter(|&secs| secs > 0) else {
+        return child.wait().context("failed to run TTS command");
     };
 
-    for file in &files {
+    // Poll instead of blocking so a wedged backend can be stopped
+    let deadline = Instant::now() + Duration::from_secs(limit);
+    loop {
+        if let Some(status) = child.try_wait().context("failed to run TTS command")? {
+            return Ok(status);
+        }
+        if Instant::now() >= deadline {
+            child.kill().ok();
+            child.wait().ok();
+            return Err(ErrorKind::Backend.error(format!(
+                "generation timed out after {limit}s and the backend process was stopped\n\
+                 Raise the limit with `--timeout <SECS>` or `qwen-tts config set generation_timeout <SECS>`"
+            )));
+        }
+        std::thread::sleep(Duration::from_millis(100));
+    }
+}
+
+pub(crate) fn play_audio(cfg: &Config, path: &Path) -> Result<()> {
+    // A --no-join directory of chunks is played chunk by chunk
+    let files = output_chunks(path);
+    if files.is_empty() {
+        output::warn(&format!("No playable audio found in {}", path.display()));
+        return Ok(());
+    }
+
+    for file in files {
         output::status("Playing", &file.to_string_lossy());
-        let status = play_single(file);
-        match status {
-            Ok(s) if s.success() => {}
-            Ok(_) => output::warn("Audio playback finished with non-zero exit code"),
-            Err(e) => output::warn(&format!("Could not play audio: {e}")),
+        match play_single(cfg, &file) {
+            Ok((player, s)) if s.success() => {
+                output::verbose("Player", &format!("{player} handled playback"));
+            }
+            Ok((player, _)) => output::warn(&format!(
+                "Audio playback with {player} finished with non-zero exit code"
+            )),
+            Err(e) => {
+                output::warn(&format!("Could not play audio: {e}"));
+                break;
+            }
         }
     }
     Ok(())
 }
 
-fn play_single(path: &Path) -> std::result::Result<std::process::ExitStatus, std::io::Error> {
-    if cfg!(target_os = "macos") {
-        Command::new("afplay")
-            .arg(path.to_string_lossy().as_ref())
-            .status()
-    } else if cfg!(target_os = "windows") {
-        Command::new("powershell")
-            .args([
+fn player_command(player: &str, path: &Path) -> Command {
+    let mut cmd = Command::new(player);
+    match player {
+        "powershell" => {
+            // Hand the path over via an environment variable instead of splicing it
+            // into the script, so quotes and non-ASCII characters survive intact.
+            cmd.args([
+                "-NoProfile",
                 "-c",
-                &format!(
-                    "(New-Object Media.SoundPlayer '{}').PlaySync()",
-                    path.display()
-                ),
+                "(New-Object Media.SoundPlayer $env:QWEN_TTS_PLAY_PATH).PlaySync()",
             ])
-            .status()
-    } else {
-        Command::new("aplay")
-            .arg(path.to_string_lossy().as_ref())
-            .status()
-            .or_else(|_| {
-                Command::new("paplay")
-                    .arg(path.to_string_lossy().as_ref())
-                    .status()
-            })
-            .or_else(|_| {
-                Command::new("ffplay")
-                    .args(["-nodisp", "-autoexit"])
-                    .arg(path.to_string_lossy().as_ref())
-                    .status()
-            })
+            .env("QWEN_TTS_PLAY_PATH", path);
+        }
+        "ffplay" => {
+            cmd.args(["-nodisp", "-autoexit"]).arg(path);
+        }
+        _ => {
+            cmd.arg(path);
+        }
+    }
+    cmd
+}
+
+/// Play with the configured `player`, or the first player in the platform's
+/// fallback chain that can be started. Returns the player used.
+fn play_single(
+    cfg: &Config,
+    path: &Path,
+) -> std::result::Result<(String, std::process::ExitStatus), std::io::Error> {
+    let players: Vec<String> = match &cfg.player {
+        Some(player) => vec![player.clone()],
+        None => platform::players().iter().map(|p| p.to_string()).collect(),
+    };
+
+    let mut last_err = None;
+    for player in players {
+        match player_command(&player, path).status() {
+            Ok(status) => return Ok((player, status)),
+            Err(e) => {
+                output::verbose("Player", &format!("{player} unavailable: {e}"));
+                last_err = Some(e);
+            }
+        }
+    }
+    Err(last_err.unwrap_or_else(|| std::io::Error::other("no audio player configured")))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn placeholders_are_filled_once() {
+        let values: Vec<String> = CUSTOM_PLACEHOLDERS
+            .iter()
+            .map(|key| key.trim_matches(['{', '}']).to_uppercase())
+            .collect();
+        let mut with_text = values.clone();
+        with_text[0] = "see {output} and {model}".to_string();
+        let cases = [
+            ("--out={output}", &values, "--out=OUTPUT"),
+            ("{text}{speed}", &values, "TEXTSPEED"),
+            ("{unknown} {text", &values, "{unknown} {text"),
+            ("{{text}}", &values, "{TEXT}"),
+            ("{text}", &with_text, "see {output} and {model}"),
+        ];
+        for (arg, values, expected) in cases {
+            assert_eq!(fill_placeholders(arg, values), expected, "{arg}");
+        }
+    }
+
+    #[test]
+    fn oom_hint_names_an_existing_4bit_variant() {
+        let oom = "torch.OutOfMemoryError: CUDA out of memory";
+        let mut cfg = Config {
+            backend: Backend::Mlx,
+            ..Config::default()
+        };
+        assert!(diagnose_backend_error(&cfg, oom).contains("model_variant base-4bit"));
+        cfg.backend = Backend::Cuda;
+        let hint = diagnose_backend_error(&cfg, oom);
+        assert!(!hint.contains("4-bit"), "{hint}");
+    }
+
+    #[test]
+    fn text_files_in_other_encodings_are_decoded() {
+        let path = std::env::temp_dir().join(format!("qwen-tts-text-{}.txt", std::process::id()));
+        let read = |bytes: &[u8]| {
+            fs::write(&path, bytes).unwrap();
+            read_text_file(&path)
+        };
+        assert_eq!(read("Grüße".as_bytes()).unwrap(), "Grüße");
+        assert_eq!(read(b"\xEF\xBB\xBFhello").unwrap(), "hello");
+        assert_eq!(read(b"\xFF\xFEh\0i\0").unwrap(), "hi");
+        assert_eq!(
+            read(b"Le caf\xE9 est tr\xE8s bon, merci beaucoup.").unwrap(),
+            "Le café est très bon, merci beaucoup."
+        );
+        let err = read(b"ab\xFF\0\x01").unwrap_err().to_string();
+        assert!(
+            err.contains("unexpected bytes 0x00 0x01 at offset 3"),
+            "{err}"
+        );
+        fs::remove_file(&path).ok();
     }
 }
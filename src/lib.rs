@@ -1,7 +1,14 @@
+pub mod audio;
+pub mod bench;
 pub mod config;
 pub mod editor;
+pub mod error;
 pub mod generate;
 pub mod models;
+pub mod normalize;
 pub mod output;
 pub mod platform;
+pub mod progress;
+pub mod stats;
 pub mod voices;
+pub mod warm;
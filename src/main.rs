@@ -1,19 +1,59 @@
 use anyhow::Result;
-use clap::{Parser, Subcommand};
+use clap::{CommandFactory, Parser, Subcommand};
 
-mod config;
-mod editor;
-mod generate;
-mod models;
-mod output;
-mod platform;
-mod voices;
+use qwen_tts::{
+    bench, config, error, generate, models, normalize, output, platform, stats, voices,
+};
 
 #[derive(Parser)]
 #[command(name = "qwen-tts")]
 #[command(about = "Cross-platform CLI for Qwen3-TTS text-to-speech with voice cloning")]
 #[command(version)]
 struct Cli {
+    /// Path to the config file (overrides QWEN_TTS_CONFIG and the default location)
+    #[arg(long, global = true, value_name = "PATH")]
+    config: Option<String>,
+
+    /// Backend for this invocation only: mlx, cuda, cpu, or custom (overrides the config)
+    #[arg(long, global = true, value_name = "BACKEND")]
+    backend: Option<platform::Backend>,
+
+    /// Never download models automatically; fail if one is missing
+    #[arg(long, global = true)]
+    no_download: bool,
+
+    /// Stop generation if the backend runs longer than this many seconds (0 = no limit)
+    #[arg(long, global = true, value_name = "SECS")]
+    timeout: Option<u64>,
+
+    /// Run up to this many backend processes at once when generating several segments
+    #[arg(long, global = true, value_name = "N", value_parser = clap::value_parser!(u16).range(1..))]
+    jobs: Option<u16>,
+
+    /// Models directory for this invocation only (overrides models_dir)
+    #[arg(long, global = true, value_name = "PATH")]
+    models_dir: Option<String>,
+
+    /// Output directory for this invocation only (overrides output_dir)
+    #[arg(long, global = true, value_name = "PATH")]
+    output_dir: Option<String>,
+
+    /// Show extra diagnostic output
+    #[arg(short, long, global = true)]
+    verbose: bool,
+
+    /// Leave staging files, segments, and other scratch files in place for debugging
+    #[arg(long, global = true)]
+    keep_temp: bool,
+
+    /// Send the backend's own output to ~/.qwen-tts/backend.log instead of the terminal
+    #[arg(long, global = true)]
+    quiet_backend: bool,
+
+    /// Append a timestamped copy of all status output to this file
+    #[arg(long, global = true, value_name = "PATH")]
+    log_file: Option<String>,
+
     #[command(subcommand)]
     command: Commands,
 }
@@ -22,12 +62,24 @@ struct Cli {
 enum Commands {
     /// Generate speech from text
     Speak {
-        /// Text to speak (positional)
+        /// Text to speak (positional). Inline tags like "[happy]" switch emotion mid-text
         text: Option<String>,
 
-        /// Read text from a file
+        /// Read text from a file (repeatable; files are joined in order)
+        #[arg(long)]
+        file: Vec<String>,
+
+        /// Spell out abbreviations first: titles, addresses, common, units, numbers, or all (comma-separated)
+        #[arg(long, value_name = "CLASSES", value_delimiter = ',')]
+        expand: Vec<normalize::Expansion>,
+
+        /// Keep numerals, currencies, and dates as written even with normalize_numbers on
         #[arg(long)]
-        file: Option<String>,
+        no_normalize_numbers: bool,
+
+        /// Generate each line of this file into its own numbered output, with a manifest.json
+        #[arg(long, value_name = "PATH", conflicts_with_all = ["text", "file", "file_pause", "append"])]
+        batch: Option<String>,
 
         /// Voice name
         #[arg(long)]
@@ -37,13 +89,69 @@ enum Commands {
         #[arg(long)]
         emotion: Option<String>,
 
-        /// Speech speed multiplier
+        /// Raw instruct prompt, used verbatim instead of the voice/emotion template
+        #[arg(long, conflicts_with = "emotion")]
+        instruct: Option<String>,
+
+        /// Silence in milliseconds inserted between multiple --file inputs
+        #[arg(long, value_name = "MS")]
+        file_pause: Option<u32>,
+
+        /// Cross-fade adjacent segments by this many milliseconds when joining them
+        #[arg(long, value_name = "MS", default_value_t = 0)]
+        crossfade: u32,
+
+        /// Speech speed multiplier, 0.25 to 4.0
         #[arg(long)]
         speed: Option<f32>,
 
-        /// Output file path
+        /// Pitch shift in semitones, -12 to 12 (only backends that support it)
+        #[arg(long, allow_hyphen_values = true, value_name = "SEMITONES")]
+        pitch: Option<f32>,
+
+        /// Output file path (with --batch, the directory for the results)
         #[arg(short, long)]
         output: Option<String>,
+
+        /// Output format when the output path has no extension: wav, mp3, flac, ogg, opus, or m4a
+        #[arg(long, value_name = "EXT")]
+        format: Option<String>,
+
+        /// Append the new audio to this existing WAV file instead of writing a new one
+        #[arg(long, value_name = "PATH", conflicts_with_all = ["output", "format"])]
+        append: Option<String>,
+
+        /// Keep the backend's per-chunk files in a directory instead of joining them into one file
+        #[arg(long, conflicts_with_all = ["format", "append", "sample_rate", "pad_start", "pad_end", "analyze", "batch", "crossfade", "file_pause", "max_seconds"])]
+        no_join: bool,
+
+        /// Resample the output to this many Hz
+        #[arg(long, value_name = "HZ", value_parser = clap::value_parser!(u32).range(8_000..=192_000))]
+        sample_rate: Option<u32>,
+
+        /// Silence in milliseconds added before the generated audio
+        #[arg(long, value_name = "MS", default_value_t = 0)]
+        pad_start: u32,
+
+        /// Silence in milliseconds added after the generated audio
+        #[arg(long, value_name = "MS", default_value_t = 0)]
+        pad_end: u32,
+
+        /// Print the output's peak and RMS levels and count clipped samples
+        #[arg(long)]
+        analyze: bool,
+
+        /// With --analyze, print the levels as JSON on stdout
+        #[arg(long, requires = "analyze")]
+        json: bool,
+
+        /// Cut the generated audio to at most this many seconds (for quick previews)
+        #[arg(long, value_name = "SECS")]
+        max_seconds: Option<f32>,
+
+        /// Only report the expected size of the --batch outputs, without generating them
+        #[arg(long, requires = "batch", conflicts_with_all = ["text", "file"])]
+        estimate: bool,
     },
 
     /// Design a voice from a text description
@@ -51,52 +159,152 @@ enum Commands {
         /// Voice description (e.g. "A deep calm British narrator")
         description: String,
 
+        /// Send the description to the model verbatim, without the design_prompt template
+        #[arg(long)]
+        raw: bool,
+
         /// Text to speak
         #[arg(long)]
         text: Option<String>,
 
-        /// Read text from a file
+        /// Read text from a file (repeatable; files are joined in order)
+        #[arg(long)]
+        file: Vec<String>,
+
+        /// Spell out abbreviations first: titles, addresses, common, units, numbers, or all (comma-separated)
+        #[arg(long, value_name = "CLASSES", value_delimiter = ',')]
+        expand: Vec<normalize::Expansion>,
+
+        /// Keep numerals, currencies, and dates as written even with normalize_numbers on
         #[arg(long)]
-        file: Option<String>,
+        no_normalize_numbers: bool,
 
-        /// Speech speed multiplier
+        /// Speech speed multiplier, 0.25 to 4.0
         #[arg(long)]
         speed: Option<f32>,
 
+        /// Pitch shift in semitones, -12 to 12 (only backends that support it)
+        #[arg(long, allow_hyphen_values = true, value_name = "SEMITONES")]
+        pitch: Option<f32>,
+
         /// Output file path
         #[arg(short, long)]
         output: Option<String>,
+
+        /// Output format when the output path has no extension: wav, mp3, flac, ogg, opus, or m4a
+        #[arg(long, value_name = "EXT")]
+        format: Option<String>,
+
+        /// Append the new audio to this existing WAV file instead of writing a new one
+        #[arg(long, value_name = "PATH", conflicts_with_all = ["output", "format"])]
+        append: Option<String>,
+
+        /// Keep the backend's per-chunk files in a directory instead of joining them into one file
+        #[arg(long, conflicts_with_all = ["format", "append", "sample_rate", "pad_start", "pad_end", "analyze"])]
+        no_join: bool,
+
+        /// Resample the output to this many Hz
+        #[arg(long, value_name = "HZ", value_parser = clap::value_parser!(u32).range(8_000..=192_000))]
+        sample_rate: Option<u32>,
+
+        /// Silence in milliseconds added before the generated audio
+        #[arg(long, value_name = "MS", default_value_t = 0)]
+        pad_start: u32,
+
+        /// Silence in milliseconds added after the generated audio
+        #[arg(long, value_name = "MS", default_value_t = 0)]
+        pad_end: u32,
+
+        /// Print the output's peak and RMS levels and count clipped samples
+        #[arg(long)]
+        analyze: bool,
+
+        /// With --analyze, print the levels as JSON on stdout
+        #[arg(long, requires = "analyze")]
+        json: bool,
     },
 
     /// Clone a voice from reference audio
     Clone {
-        /// Path to reference audio file
+        /// Path to reference audio file (repeatable; clips are merged in order)
         #[arg(long = "ref")]
-        ref_audio: Option<String>,
+        ref_audio: Vec<String>,
 
-        /// Transcript of the reference audio
+        /// Transcript of the reference audio (of all clips, in order, with several --ref)
         #[arg(long)]
         ref_text: Option<String>,
 
+        /// Transcribe the reference audio with speech recognition when no transcript is available
+        #[arg(long, conflicts_with = "ref_text")]
+        auto_transcript: bool,
+
         /// Use a saved voice by name
         #[arg(long)]
         voice: Option<String>,
 
+        /// Model variant for this run (defaults to the saved voice's preferred variant, then the config)
+        #[arg(long, value_parser = models::parse_variant)]
+        variant: Option<String>,
+
         /// Text to speak
         #[arg(long)]
         text: Option<String>,
 
-        /// Read text from a file
+        /// Read text from a file (repeatable; files are joined in order)
         #[arg(long)]
-        file: Option<String>,
+        file: Vec<String>,
 
-        /// Speech speed multiplier
+        /// Spell out abbreviations first: titles, addresses, common, units, numbers, or all (comma-separated)
+        #[arg(long, value_name = "CLASSES", value_delimiter = ',')]
+        expand: Vec<normalize::Expansion>,
+
+        /// Keep numerals, currencies, and dates as written even with normalize_numbers on
+        #[arg(long)]
+        no_normalize_numbers: bool,
+
+        /// Speech speed multiplier, 0.25 to 4.0
         #[arg(long)]
         speed: Option<f32>,
 
+        /// Pitch shift in semitones, -12 to 12 (only backends that support it)
+        #[arg(long, allow_hyphen_values = true, value_name = "SEMITONES")]
+        pitch: Option<f32>,
+
         /// Output file path
         #[arg(short, long)]
         output: Option<String>,
+
+        /// Output format when the output path has no extension: wav, mp3, flac, ogg, opus, or m4a
+        #[arg(long, value_name = "EXT")]
+        format: Option<String>,
+
+        /// Append the new audio to this existing WAV file instead of writing a new one
+        #[arg(long, value_name = "PATH", conflicts_with_all = ["output", "format"])]
+        append: Option<String>,
+
+        /// Keep the backend's per-chunk files in a directory instead of joining them into one file
+        #[arg(long, conflicts_with_all = ["format", "append", "sample_rate", "pad_start", "pad_end", "analyze"])]
+        no_join: bool,
+
+        /// Resample the output to this many Hz
+        #[arg(long, value_name = "HZ", value_parser = clap::value_parser!(u32).range(8_000..=192_000))]
+        sample_rate: Option<u32>,
+
+        /// Silence in milliseconds added before the generated audio
+        #[arg(long, value_name = "MS", default_value_t = 0)]
+        pad_start: u32,
+
+        /// Silence in milliseconds added after the generated audio
+        #[arg(long, value_name = "MS", default_value_t = 0)]
+        pad_end: u32,
+
+        /// Print the output's peak and RMS levels and count clipped samples
+        #[arg(long)]
+        analyze: bool,
+
+        /// With --analyze, print the levels as JSON on stdout
+        #[arg(long, requires = "analyze")]
+        json: bool,
     },
 
     /// Manage saved voices
@@ -116,25 +324,101 @@ enum Commands {
         #[command(subcommand)]
         action: ConfigAction,
     },
+
+    /// Show platform detection and availability of required tools
+    Platform,
+
+    /// Print where config, models, voices, and outputs live
+    #[command(alias = "where")]
+    Path {
+        /// Which path: config, base, models, voices, or output (all if omitted)
+        kind: Option<String>,
+
+        /// Reveal the directory in the file manager
+        #[arg(long, requires = "kind")]
+        open: bool,
+    },
+
+    /// Benchmark generation speed for the current backend and variant
+    Bench,
+
+    /// Show characters synthesized per day, voice, and model
+    Stats {
+        /// Only count days from this date on (YYYY-MM-DD, UTC)
+        #[arg(long, value_name = "DATE")]
+        since: Option<String>,
+
+        /// Print machine-readable JSON instead of a table
+        #[arg(long)]
+        json: bool,
+    },
+
+    /// Show diagnostic logs
+    Logs {
+        #[command(subcommand)]
+        action: LogsAction,
+    },
+
+    /// Print a shell completion script
+    Completions {
+        /// Shell to generate completions for
+        shell: clap_complete::Shell,
+    },
 }
 
 #[derive(Subcommand)]
 enum VoicesAction {
     /// List all saved voices
-    List,
+    List {
+        /// Only show voices without a transcript
+        #[arg(long)]
+        missing_transcripts: bool,
+    },
+
+    /// List the preset voices built into the configured model, and the saved ones
+    Builtin {
+        /// Re-read the model's voices instead of using the cached list
+        #[arg(long)]
+        refresh: bool,
+    },
 
     /// Enroll a new voice from reference audio
     Add {
         /// Name for the voice
         name: String,
 
-        /// Path to reference audio file (.wav)
+        /// Path to reference audio file (.wav; repeatable, clips are merged in order)
         #[arg(long = "ref")]
-        ref_audio: String,
+        #[cfg_attr(feature = "url-import", arg(required_unless_present = "url"))]
+        #[cfg_attr(not(feature = "url-import"), arg(required = true))]
+        ref_audio: Vec<String>,
+
+        /// Download the reference audio from a media URL (uses yt-dlp / ffmpeg)
+        #[cfg(feature = "url-import")]
+        #[arg(long, conflicts_with = "ref_audio")]
+        url: Option<String>,
 
-        /// Transcript of the reference audio
+        /// Transcript of the reference audio (of all clips, in order, with several --ref)
         #[arg(long)]
         transcript: Option<String>,
+
+        /// Generate the transcript with speech recognition (reviewed in the editor)
+        #[arg(long, conflicts_with = "transcript")]
+        auto_transcript: bool,
+
+        /// Model variant `clone --voice` should use for this voice, e.g. "custom"
+        #[arg(long, value_parser = models::parse_variant)]
+        variant: Option<String>,
+    },
+
+    /// Hear a saved voice speak a standard sentence
+    Preview {
+        /// Name of the voice to preview
+        name: String,
+
+        /// Speak this text instead of the standard sentence
+        #[arg(long)]
+        text: Option<String>,
     },
 
     /// Remove a saved voice
@@ -147,13 +431,51 @@ enum VoicesAction {
 #[derive(Subcommand)]
 enum ModelsAction {
     /// List installed models
-    List,
+    List {
+        /// Print machine-readable JSON instead of a table
+        #[arg(long)]
+        json: bool,
+    },
 
     /// Download a model
     Download {
         /// Model variant: "pro" or "lite"
         #[arg(long, default_value = "pro")]
         variant: String,
+
+        /// Install from a local model directory or .tar archive instead of downloading
+        #[arg(long, value_name = "PATH")]
+        from: Option<String>,
+
+        /// Download every variant, summarizing any failures at the end
+        #[arg(long, conflicts_with = "from")]
+        all: bool,
+
+        /// Download this revision (commit, branch, or tag) instead of model_revision
+        #[arg(long, conflicts_with_all = ["from", "all"])]
+        revision: Option<String>,
+
+        /// Only report how much disk the download would use
+        #[arg(long, conflicts_with = "from")]
+        estimate: bool,
+    },
+
+    /// Show the repository a variant resolves to and whether it is installed
+    Which {
+        /// Model variant (defaults to configured variant)
+        #[arg(long)]
+        variant: Option<String>,
+    },
+
+    /// Remove directories in models_dir that are not a complete, known variant
+    Gc {
+        /// Remove without asking
+        #[arg(short, long)]
+        yes: bool,
+
+        /// Only list what would be removed
+        #[arg(long, conflicts_with = "yes")]
+        dry_run: bool,
     },
 
     /// Update model to the latest release
@@ -162,12 +484,27 @@ enum ModelsAction {
         #[arg(long)]
         variant: Option<String>,
     },
+
+    /// Fetch the variant list from models_manifest_url, adding newly published variants
+    RefreshManifest,
+}
+
+#[derive(Subcommand)]
+enum LogsAction {
+    /// Print the error and backend output from the most recent failed command
+    Last,
+    /// Print the backend output of the latest command run with quiet_backend
+    Backend,
 }
 
 #[derive(Subcommand)]
 enum ConfigAction {
     /// Show current configuration
-    Show,
+    Show {
+        /// Print the configuration as JSON instead of TOML
+        #[arg(long)]
+        json: bool,
+    },
 
     /// Set a configuration value
     Set {
@@ -178,88 +515,391 @@ enum ConfigAction {
     },
 
     /// Initialize configuration (auto-detect platform)
-    Init,
+    Init {
+        /// Overwrite an existing config with defaults without asking
+        #[arg(long)]
+        force: bool,
+
+        /// Never prompt; keep an existing config as it is (for scripted setup)
+        #[arg(short = 'y', long, visible_alias = "yes", conflicts_with = "force")]
+        keep: bool,
+    },
 }
 
 fn main() {
+    let mut args = std::env::args().skip(1);
+    if args.next().as_deref() == Some(COMPLETE_COMMAND) {
+        if args.next().as_deref() == Some("voices") {
+            voices::print_names();
+        }
+        return;
+    }
     let cli = Cli::parse();
 
     if let Err(e) = run(cli) {
-        output::error(&format!("{e:#}"));
-        std::process::exit(1);
+        let message = format!("{e:#}");
+        output::error(&message);
+        if let Err(save) = output::save_last_error(&config::last_error_path(), &message) {
+            output::verbose("Logs", &format!("{save:#}"));
+        }
+        std::process::exit(error::exit_code(&e));
     }
 }
 
 fn run(cli: Cli) -> Result<()> {
+    if let Some(path) = &cli.config {
+        config::set_config_override(path);
+    }
+    output::set_verbose(cli.verbose);
+    if cli.no_download {
+        config::set_no_download();
+    }
+    if cli.keep_temp {
+        generate::set_keep_temp();
+    }
+    if cli.quiet_backend {
+        config::set_quiet_backend();
+    }
+    if let Some(backend) = cli.backend {
+        config::set_backend_override(backend);
+    }
+    if let Some(secs) = cli.timeout {
+        config::set_timeout_override(secs);
+    }
+    if let Some(jobs) = cli.jobs {
+        config::set_jobs_override(usize::from(jobs));
+    }
+    if let Some(dir) = &cli.models_dir {
+        config::set_models_dir_override(dir);
+    }
+    if let Some(dir) = &cli.output_dir {
+        config::set_output_dir_override(dir);
+    }
+
+    let existing = config::read_existing_with_project();
+    // The environment overrides the global and project config
+    let theme = std::env::var("QWEN_TTS_THEME")
+        .ok()
+        .filter(|t| !t.is_empty())
+        .or_else(|| existing.as_ref().and_then(|cfg| cfg.theme.clone()));
+    if let Some(theme) = theme {
+        match theme.parse() {
+            Ok(theme) => output::set_theme(theme),
+            Err(e) => output::warn(&format!("{e:#}; using the default theme")),
+        }
+    }
+
+    let log_file = cli
+        .log_file
+        .clone()
+        .or_else(|| existing.and_then(|cfg| cfg.log_file));
+    if let Some(path) = log_file {
+        output::init_log(&config::expand_path(&path))?;
+    }
+
     match cli.command {
         Commands::Speak {
             text,
             file,
+            expand,
+            no_normalize_numbers,
+            batch,
             voice,
             emotion,
+            instruct,
+            file_pause,
+            crossfade,
             speed,
+            pitch,
             output,
+            format,
+            append,
+            no_join,
+            sample_rate,
+            pad_start,
+            pad_end,
+            analyze,
+            json,
+            max_seconds,
+            estimate,
         } => generate::speak(generate::SpeakArgs {
             text,
             file,
+            batch,
             voice,
             emotion,
+            instruct,
+            file_pause,
             speed,
+            pitch,
             output,
+            format,
+            append,
+            no_join,
+            estimate,
+            normalize: normalize::Options {
+                expand,
+                keep_numbers: no_normalize_numbers,
+                numbers: None,
+            },
+            post: generate::PostProcess {
+                pad_start_ms: pad_start,
+                pad_end_ms: pad_end,
+                max_secs: max_seconds,
+                sample_rate,
+                analyze: analyze.then_some(if json {
+                    generate::LevelReport::Json
+                } else {
+                    generate::LevelReport::Text
+                }),
+                crossfade_ms: crossfade,
+            },
         }),
 
         Commands::Design {
             description,
+            raw,
             text,
             file,
+            expand,
+            no_normalize_numbers,
             speed,
+            pitch,
             output,
+            format,
+            append,
+            no_join,
+            sample_rate,
+            pad_start,
+            pad_end,
+            analyze,
+            json,
         } => generate::design(generate::DesignArgs {
             description,
             text,
             file,
             speed,
+            pitch,
             output,
+            format,
+            append,
+            no_join,
+            raw,
+            normalize: normalize::Options {
+                expand,
+                keep_numbers: no_normalize_numbers,
+                numbers: None,
+            },
+            post: generate::PostProcess {
+                pad_start_ms: pad_start,
+                pad_end_ms: pad_end,
+                max_secs: None,
+                sample_rate,
+                analyze: analyze.then_some(if json {
+                    generate::LevelReport::Json
+                } else {
+                    generate::LevelReport::Text
+                }),
+                crossfade_ms: 0,
+            },
         }),
 
         Commands::Clone {
             ref_audio,
             ref_text,
+            auto_transcript,
             voice,
+            variant,
             text,
             file,
+            expand,
+            no_normalize_numbers,
             speed,
+            pitch,
             output,
+            format,
+            append,
+            no_join,
+            sample_rate,
+            pad_start,
+            pad_end,
+            analyze,
+            json,
         } => generate::clone(generate::CloneArgs {
             ref_audio,
             ref_text,
+            auto_transcript,
             voice,
             text,
             file,
             speed,
+            pitch,
             output,
+            format,
+            append,
+            no_join,
+            variant,
+            normalize: normalize::Options {
+                expand,
+                keep_numbers: no_normalize_numbers,
+                numbers: None,
+            },
+            post: generate::PostProcess {
+                pad_start_ms: pad_start,
+                pad_end_ms: pad_end,
+                max_secs: None,
+                sample_rate,
+                analyze: analyze.then_some(if json {
+                    generate::LevelReport::Json
+                } else {
+                    generate::LevelReport::Text
+                }),
+                crossfade_ms: 0,
+            },
         }),
 
         Commands::Voices { action } => match action {
-            VoicesAction::List => voices::list(),
+            VoicesAction::List {
+                missing_transcripts,
+            } => voices::list(missing_transcripts),
             VoicesAction::Add {
                 name,
                 ref_audio,
+                #[cfg(feature = "url-import")]
+                url,
                 transcript,
-            } => voices::add(&name, &ref_audio, transcript.as_deref()),
+                auto_transcript,
+                variant,
+            } => {
+                #[cfg(feature = "url-import")]
+                if let Some(url) = url {
+                    return voices::add_from_url(
+                        &name,
+                        &url,
+                        transcript.as_deref(),
+                        auto_transcript,
+                        variant.as_deref(),
+                    );
+                }
+                voices::add(
+                    &name,
+                    &ref_audio,
+                    transcript.as_deref(),
+                    auto_transcript,
+                    variant.as_deref(),
+                )
+            }
+            VoicesAction::Builtin { refresh } => voices::builtin(refresh),
+            VoicesAction::Preview { name, text } => voices::preview(&name, text.as_deref()),
             VoicesAction::Remove { name } => voices::remove(&name),
         },
 
         Commands::Models { action } => match action {
-            ModelsAction::List => models::list(),
-            ModelsAction::Download { variant } => models::download(&variant),
+            ModelsAction::List { json } => models::list(json),
+            ModelsAction::Download {
+                variant,
+                from,
+                all,
+                revision,
+                estimate,
+            } => match from {
+                Some(from) => models::install_from(&variant, &from),
+                None if estimate && all => models::estimate_download(&models::variants(), None),
+                None if estimate => models::estimate_download(&[&variant], revision.as_deref()),
+                None if all => models::download_all(),
+                None => models::download(&variant, revision.as_deref()),
+            },
+            ModelsAction::Which { variant } => models::which(variant.as_deref()),
+            ModelsAction::Gc { yes, dry_run } => models::gc(yes, dry_run),
             ModelsAction::Update { variant } => models::update(variant.as_deref()),
+            ModelsAction::RefreshManifest => models::refresh_manifest(),
         },
 
         Commands::Config { action } => match action {
-            ConfigAction::Show => config::show(),
+            ConfigAction::Show { json } => config::show(json),
             ConfigAction::Set { key, value } => config::set(&key, &value),
-            ConfigAction::Init => config::init(),
+            ConfigAction::Init { force, keep } => config::init(force, keep),
         },
+
+        Commands::Platform => platform::show(),
+
+        Commands::Path { kind, open } => config::show_path(kind.as_deref(), open),
+
+        Commands::Bench => bench::run(),
+
+        Commands::Stats { since, json } => stats::show(json, since.as_deref()),
+
+        Commands::Logs { action } => match action {
+            LogsAction::Last => output::print_last_error(&config::last_error_path()),
+            LogsAction::Backend => output::print_log(
+                &config::backend_log_path(),
+                "No backend output recorded yet (see quiet_backend)",
+            ),
+        },
+
+        Commands::Completions { shell } => {
+            print_completions(shell);
+            Ok(())
+        }
+    }
+}
+
+/// Hidden command the completion scripts run to list dynamic values. It is
+/// handled before argument parsing so it never shows up in completions.
+const COMPLETE_COMMAND: &str = "__complete";
+
+/// Completion script for `shell`, with `--voice` completing saved voices
+/// through `qwen-tts __complete voices` where the shell allows it.
+fn print_completions(shell: clap_complete::Shell) {
+    use clap_complete::Shell;
+
+    let mut script = Vec::new();
+    clap_complete::generate(shell, &mut Cli::command(), "qwen-tts", &mut script);
+    let mut script = String::from_utf8_lossy(&script).into_owned();
+    match shell {
+        Shell::Bash => {
+            script = script.replace("complete -F _qwen__tts ", "complete -F _qwen_tts_voices ");
+            script.push_str(&format!(
+                r#"
+_qwen_tts_voices() {{
+    if [[ "${{COMP_WORDS[COMP_CWORD-1]}}" == "--voice" ]]; then
+        COMPREPLY=( $(compgen -W "$(qwen-tts {COMPLETE_COMMAND} voices 2>/dev/null)" -- "${{COMP_WORDS[COMP_CWORD]}}") )
+        return 0
+    fi
+    _qwen__tts "$@"
+}}
+"#
+            ));
+        }
+        Shell::Zsh => {
+            script = script.replace(":VOICE:_default'", ":VOICE:_qwen-tts_voices'");
+            let helper = format!(
+                r#"(( $+functions[_qwen-tts_voices] )) ||
+_qwen-tts_voices() {{
+    local -a voices
+    voices=(${{(f)"$(qwen-tts {COMPLETE_COMMAND} voices 2>/dev/null)"}})
+    _describe -t voices 'saved voice' voices
+}}
+
+"#
+            );
+            if let Some(at) = script.find("if [ \"$funcstack[1]\"") {
+                script.insert_str(at, &helper);
+            }
+        }
+        Shell::Fish => {
+            script = script
+                .lines()
+                .map(|line| {
+                    if line.contains(" -l voice ") && line.ends_with(" -r") {
+                        format!("{line} -f -a \"(qwen-tts {COMPLETE_COMMAND} voices)\"\n")
+                    } else {
+                        format!("{line}\n")
+                    }
+                })
+                .collect();
+        }
+        _ => {}
     }
+    print!("{script}");
 }
@@ -1,31 +1,35 @@
+use std::collections::{BTreeMap, HashSet};
 use std::fs;
-use std::io::{self, Write};
+use std::io::{IsTerminal, Write};
 use std::path::{Path, PathBuf};
 use std::process::Command;
+use std::sync::OnceLock;
+use std::time::{SystemTime, UNIX_EPOCH};
 
 use anyhow::{Context, Result};
 use colored::Colorize;
+use serde::{Deserialize, Serialize};
 
 use crate::config::{self, Config};
+use crate::error::ErrorKind;
 use crate::output;
-use crate::platform::Backend;
+use crate::platform::{self, Backend};
+use crate::progress::{self, Event};
 
-fn prompt_yn(question: &str, default_yes: bool) -> bool {
-    let hint = if default_yes { "[Y/n]" } else { "[y/N]" };
-    eprint!("{question} {hint} ");
-    io::stderr().flush().ok();
-    let mut input = String::new();
-    if io::stdin().read_line(&mut input).is_err() {
-        return default_yes;
-    }
-    let input = input.trim().to_lowercase();
-    if input.is_empty() {
-        return default_yes;
-    }
-    input.starts_with('y')
-}
+/// Model variants that can be downloaded and selected with `model_variant`.
+pub const VARIANTS: &[&str] = &[
+    "base",
+    "base-4bit",
+    "custom",
+    "custom-4bit",
+    "design",
+    "design-4bit",
+];
 
 pub fn repo_id(backend: Backend, variant: &str) -> Result<&'static str> {
+    if let Some(repo) = manifest_repo(backend, variant) {
+        return Ok(repo);
+    }
     match (backend, variant) {
         // Base: standard TTS + voice cloning (0.6B)
         (Backend::Mlx, "base") => Ok("mlx-community/Qwen3-TTS-12Hz-0.6B-Base-bf16"),
@@ -48,47 +52,433 @@ pub fn repo_id(backend: Backend, variant: &str) -> Result<&'static str> {
         (Backend::Mlx, "lite") => Ok("mlx-community/Qwen3-TTS-12Hz-0.6B-Base-4bit"),
         (_, "pro") => Ok("Qwen/Qwen3-TTS-12Hz-0.6B-Base"),
         (_, "lite") => Ok("Qwen/Qwen3-TTS-12Hz-0.6B-Base"),
-        _ => anyhow::bail!(
-            "unknown variant: {variant}\nAvailable: base, base-4bit, custom, custom-4bit, design, design-4bit"
-        ),
+        _ => Err(ErrorKind::Config.error(format!(
+            "unknown variant: {variant}\nAvailable: {}",
+            variants().join(", ")
+        ))),
+    }
+}
+
+/// Variant → repository map fetched from `models_manifest_url`, keyed by
+/// backend name ("mlx", "cuda", ...) or "default" for every backend.
+type Manifest = BTreeMap<String, BTreeMap<String, String>>;
+
+/// Manifest key whose entries apply to every backend.
+const MANIFEST_DEFAULT_KEY: &str = "default";
+
+/// ~/.qwen-tts/cache/models_manifest.json: the last fetched manifest.
+fn manifest_path() -> PathBuf {
+    config::base_dir()
+        .join("cache")
+        .join("models_manifest.json")
+}
+
+/// The cached manifest, read once per run; empty when none was fetched.
+/// Variants with unsafe names are dropped.
+fn manifest() -> &'static Manifest {
+    static MANIFEST: OnceLock<Manifest> = OnceLock::new();
+    MANIFEST.get_or_init(|| {
+        let mut manifest: Manifest = fs::read_to_string(manifest_path())
+            .ok()
+            .and_then(|text| serde_json::from_str(&text).ok())
+            .unwrap_or_default();
+        for repos in manifest.values_mut() {
+            repos.retain(|variant, _| is_valid_variant_name(variant));
+        }
+        manifest
+    })
+}
+
+/// Whether `name` is safe as a directory under `models_dir`:
+/// `[a-z0-9][a-z0-9._-]*`.
+fn is_valid_variant_name(name: &str) -> bool {
+    let mut chars = name.chars();
+    chars
+        .next()
+        .is_some_and(|c| c.is_ascii_lowercase() || c.is_ascii_digit())
+        && chars.all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || "._-".contains(c))
+}
+
+/// Repository the manifest gives for `variant`, preferring an entry for
+/// `backend` over the default one.
+fn manifest_repo(backend: Backend, variant: &str) -> Option<&'static str> {
+    let manifest = manifest();
+    [backend.to_string(), MANIFEST_DEFAULT_KEY.to_string()]
+        .iter()
+        .find_map(|key| manifest.get(key)?.get(variant))
+        .map(String::as_str)
+}
+
+/// Built-in variants followed by any the manifest adds.
+pub fn variants() -> Vec<&'static str> {
+    let mut all = VARIANTS.to_vec();
+    for variant in manifest().values().flat_map(BTreeMap::keys) {
+        if !all.contains(&variant.as_str()) {
+            all.push(variant);
+        }
+    }
+    all
+}
+
+/// Parse a `--variant` value against [`variants`].
+pub fn parse_variant(value: &str) -> std::result::Result<String, String> {
+    if variants().contains(&value) {
+        Ok(value.to_string())
+    } else {
+        Err(format!("expected one of: {}", variants().join(", ")))
     }
 }
 
-fn model_dir(cfg: &Config, variant: &str) -> PathBuf {
+/// Prints the body of the URL in argv[1], giving up after argv[2] seconds.
+const FETCH_URL_SCRIPT: &str = "import sys, urllib.request; \
+    sys.stdout.buffer.write(urllib.request.urlopen(sys.argv[1], \
+    timeout=float(sys.argv[2]) or None).read())";
+
+/// Fetch the manifest at `models_manifest_url` into the local cache. With no
+/// URL configured, drop any cached manifest so the built-in map applies.
+pub fn refresh_manifest() -> Result<()> {
+    let cfg = config::load_or_default();
+    let path = manifest_path();
+    let Some(url) = &cfg.models_manifest_url else {
+        if path.exists() {
+            fs::remove_file(&path)
+                .with_context(|| format!("failed to remove {}", path.display()))?;
+            output::success("Removed the cached models manifest; using the built-in variants");
+        } else {
+            output::status(
+                "Manifest",
+                "models_manifest_url is not set; using the built-in variants",
+            );
+        }
+        return Ok(());
+    };
+
+    let python = config::expand_path(&cfg.python_path);
+    output::status("Fetching", &format!("{url}..."));
+    let result = Command::new(&python)
+        .args([
+            "-c",
+            FETCH_URL_SCRIPT,
+            url,
+            &cfg.network_timeout.to_string(),
+        ])
+        .envs(cfg.network_env())
+        .output()
+        .with_context(|| format!("failed to run {}", python.display()))?;
+    if !result.status.success() {
+        let stderr = String::from_utf8_lossy(&result.stderr);
+        return Err(ErrorKind::Model.error(format!(
+            "could not fetch the models manifest from {url}: {}",
+            stderr.trim().lines().last().unwrap_or("unknown error")
+        )));
+    }
+    let manifest: Manifest = serde_json::from_slice(&result.stdout).map_err(|e| {
+        ErrorKind::Config.error(format!(
+            "{url} is not a models manifest ({e}); expected JSON like \
+             {{\"default\": {{\"base\": \"Qwen/Qwen3-TTS-12Hz-0.6B-Base\"}}}}"
+        ))
+    })?;
+    for (key, repos) in &manifest {
+        if key != MANIFEST_DEFAULT_KEY && key.parse::<Backend>().is_err() {
+            return Err(ErrorKind::Config.error(format!(
+                "models manifest: unknown backend \"{key}\" (expected a backend name or \"{MANIFEST_DEFAULT_KEY}\")"
+            )));
+        }
+        if let Some(variant) = repos.keys().find(|v| !is_valid_variant_name(v)) {
+            return Err(ErrorKind::Config.error(format!(
+                "models manifest: \"{variant}\" under {key} is not a valid variant name \
+                 (lowercase letters, digits, '.', '_' and '-', starting with a letter or digit)"
+            )));
+        }
+        if let Some((variant, repo)) = repos.iter().find(|(_, repo)| !repo.contains('/')) {
+            return Err(ErrorKind::Config.error(format!(
+                "models manifest: \"{repo}\" for {key}/{variant} is not a repository id (owner/name)"
+            )));
+        }
+    }
+
+    let dir = path.parent().expect("manifest path has a parent");
+    fs::create_dir_all(dir).with_context(|| format!("failed to create {}", dir.display()))?;
+    let text = serde_json::to_string_pretty(&manifest).context("failed to serialize manifest")?;
+    fs::write(&path, text + "\n").with_context(|| format!("failed to write {}", path.display()))?;
+
+    let added: std::collections::BTreeSet<&str> = manifest
+        .values()
+        .flat_map(BTreeMap::keys)
+        .map(String::as_str)
+        .filter(|v| !VARIANTS.contains(v))
+        .collect();
+    output::success(&format!(
+        "Models manifest updated ({} entries{})",
+        manifest.values().map(BTreeMap::len).sum::<usize>(),
+        if added.is_empty() {
+            String::new()
+        } else {
+            format!("; new variants: {}", Vec::from_iter(added).join(", "))
+        }
+    ));
+    Ok(())
+}
+
+/// Working memory beyond the weights (speech tokenizer, KV cache, audio
+/// decoder, CUDA context), in MiB.
+const VRAM_OVERHEAD_MIB: u64 = 1024;
+
+/// Rough GPU memory needed to run `variant` on `backend`, in MiB, estimated
+/// from the parameter count and precision in its repository name.
+pub fn estimated_vram_mib(backend: Backend, variant: &str) -> Option<u64> {
+    let repo = repo_id(backend, variant).ok()?;
+    Some(estimated_weight_bytes(repo)? / (1024 * 1024) + VRAM_OVERHEAD_MIB)
+}
+
+/// Size of the weights in `repo`, from the parameter count and precision
+/// in its name.
+fn estimated_weight_bytes(repo: &str) -> Option<u64> {
+    let params_millions: u64 = if repo.contains("1.7B") {
+        1700
+    } else if repo.contains("0.6B") {
+        600
+    } else {
+        return None;
+    };
+    let bits: u64 = if repo.contains("4bit") { 4 } else { 16 };
+    Some(params_millions * 1_000_000 * bits / 8)
+}
+
+/// The 4-bit counterpart of `variant`, when `backend` maps it to a
+/// repository of its own. On CUDA/CPU the built-in map has none, since
+/// every variant loads the same full-precision model there.
+pub fn quantized_variant(backend: Backend, variant: &str) -> Option<String> {
+    if variant.ends_with("-4bit") {
+        return None;
+    }
+    let quantized = format!("{variant}-4bit");
+    let repo = repo_id(backend, variant).ok()?;
+    (variants().contains(&quantized.as_str()) && repo_id(backend, &quantized).ok()? != repo)
+        .then_some(quantized)
+}
+
+/// `variant` if it fits in `free_mib` of GPU memory, else its 4-bit
+/// counterpart if one exists and fits. `None` when neither is expected to
+/// fit.
+pub fn fitting_variant(backend: Backend, variant: &str, free_mib: u64) -> Option<String> {
+    std::iter::once(variant.to_string())
+        .chain(quantized_variant(backend, variant))
+        .find(|v| estimated_vram_mib(backend, v).is_some_and(|need| need <= free_mib))
+}
+
+/// Variant to start a new setup on: the configured one, or on a small GPU
+/// its 4-bit counterpart when only that fits in free memory.
+pub fn suggested_variant(cfg: &Config) -> String {
+    if cfg.backend == Backend::Cuda {
+        let fitting = platform::gpu_free_memory_mib()
+            .and_then(|free| fitting_variant(cfg.backend, &cfg.model_variant, free));
+        if let Some(variant) = fitting {
+            return variant;
+        }
+    }
+    cfg.model_variant.clone()
+}
+
+/// Metadata stored as `.qwen-tts-model.toml` inside each installed model directory.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ModelMeta {
+    pub variant: String,
+    pub repo: String,
+    /// "huggingface", "git", or the local path the model was installed from
+    pub source: String,
+    /// Pinned revision (commit, branch, or tag) the model was downloaded at
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub revision: Option<String>,
+    pub installed_at: u64,
+}
+
+const META_FILE: &str = ".qwen-tts-model.toml";
+/// Name used by older releases. It clashes with the project config file, so
+/// it is only read, and removed when the metadata is rewritten.
+const LEGACY_META_FILE: &str = ".qwen-tts.toml";
+
+fn write_meta(
+    dest: &Path,
+    variant: &str,
+    repo: &str,
+    source: &str,
+    revision: Option<&str>,
+) -> Result<()> {
+    let meta = ModelMeta {
+        variant: variant.to_string(),
+        repo: repo.to_string(),
+        source: source.to_string(),
+        revision: revision.map(str::to_string),
+        installed_at: SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_secs())
+            .unwrap_or(0),
+    };
+    let text = toml::to_string_pretty(&meta).context("failed to serialize model metadata")?;
+    let path = dest.join(META_FILE);
+    fs::write(&path, text).with_context(|| format!("failed to write {}", path.display()))?;
+    fs::remove_file(dest.join(LEGACY_META_FILE)).ok();
+    Ok(())
+}
+
+fn read_meta(dest: &Path) -> Option<ModelMeta> {
+    let text = fs::read_to_string(dest.join(META_FILE))
+        .or_else(|_| fs::read_to_string(dest.join(LEGACY_META_FILE)))
+        .ok()?;
+    toml::from_str(&text).ok()
+}
+
+pub(crate) fn model_dir(cfg: &Config, variant: &str) -> PathBuf {
     config::expand_path(&cfg.models_dir).join(variant)
 }
 
-fn is_model_installed(cfg: &Config, variant: &str) -> bool {
+/// File extensions that hold model weights.
+const WEIGHT_EXTENSIONS: &[&str] = &["safetensors", "bin", "npz", "pt", "gguf"];
+
+/// Describe what is missing from a model directory, or `None` if it looks
+/// complete (a `config.json` plus at least one real weights file).
+pub fn incomplete_reason(dir: &Path) -> Option<String> {
+    if !dir.join("config.json").is_file() {
+        return Some("config.json is missing".to_string());
+    }
+    let has_weights = fs::read_dir(dir)
+        .map(|entries| {
+            entries.flatten().map(|e| e.path()).any(|p| {
+                p.extension()
+                    .and_then(|e| e.to_str())
+                    .is_some_and(|e| WEIGHT_EXTENSIONS.contains(&e))
+                    && !is_lfs_pointer(&p)
+            })
+        })
+        .unwrap_or(false);
+    if !has_weights {
+        return Some("no weights files found".to_string());
+    }
+    None
+}
+
+pub fn is_model_installed(cfg: &Config, variant: &str) -> bool {
     let dir = model_dir(cfg, variant);
-    dir.exists()
-        && fs::read_dir(&dir)
-            .map(|mut d| d.next().is_some())
-            .unwrap_or(false)
+    dir.exists() && incomplete_reason(&dir).is_none()
+}
+
+/// Downloads the repo in argv[1] into the directory in argv[2], at the
+/// revision in argv[3] if given.
+const SNAPSHOT_DOWNLOAD_SCRIPT: &str = "import sys; \
+    from huggingface_hub import snapshot_download; \
+    snapshot_download(sys.argv[1], local_dir=sys.argv[2], \
+    revision=sys.argv[3] if len(sys.argv) > 3 else None)";
+
+/// Run a networked command built by `make`, retrying with backoff up to
+/// `network_retries` times. `before_retry` runs before each new attempt,
+/// e.g. to clear a partial clone. Returns the last exit status.
+fn run_with_retries(
+    cfg: &Config,
+    what: &str,
+    mut make: impl FnMut() -> Command,
+    mut before_retry: impl FnMut(),
+) -> Result<std::process::ExitStatus> {
+    let attempts = cfg.network_retries + 1;
+    for attempt in 1..=attempts {
+        if attempt > 1 {
+            before_retry();
+        }
+        let mut cmd = make();
+        cmd.envs(cfg.network_env())
+            .envs(cfg.hf_auth_env())
+            .stdout(std::process::Stdio::inherit())
+            .stderr(std::process::Stdio::inherit());
+        let status = cmd
+            .status()
+            .with_context(|| format!("failed to run {what}"))?;
+        if status.success() || attempt == attempts {
+            return Ok(status);
+        }
+        let delay = 2u64.pow(attempt.min(5));
+        output::warn(&format!(
+            "{what} failed (attempt {attempt}/{attempts}), retrying in {delay}s..."
+        ));
+        std::thread::sleep(std::time::Duration::from_secs(delay));
+    }
+    unreachable!("at least one attempt is made")
+}
+
+/// Run the download `f` while reporting the size of `dest` about once a
+/// second as progress events.
+fn with_byte_progress<T>(repo: &str, dest: &Path, f: impl FnOnce() -> T) -> T {
+    use std::sync::atomic::{AtomicBool, Ordering};
+
+    let done = AtomicBool::new(false);
+    std::thread::scope(|scope| {
+        let watcher = scope.spawn(|| {
+            while !done.load(Ordering::Relaxed) {
+                if let Ok(bytes) = dir_size(dest) {
+                    progress::emit(Event::DownloadBytes { repo, bytes });
+                }
+                std::thread::park_timeout(std::time::Duration::from_secs(1));
+            }
+        });
+        let result = f();
+        done.store(true, Ordering::Relaxed);
+        watcher.thread().unpark();
+        result
+    })
+}
+
+/// Gated repos answer 401/403 without a token; point at the fix when none
+/// is configured.
+fn gated_hint(cfg: &Config) -> &'static str {
+    if cfg.effective_hf_token().is_some() {
+        ""
+    } else {
+        "\nIf the repository is gated, accept its terms on huggingface.co and provide an \
+         access token with `qwen-tts config set hf_token <token>` or HF_TOKEN"
+    }
 }
 
 /// Try downloading with Python huggingface_hub, fall back to git clone.
-fn download_repo(cfg: &Config, repo: &str, dest: &PathBuf) -> Result<()> {
+/// Returns the source that succeeded ("huggingface" or "git").
+fn download_repo(
+    cfg: &Config,
+    repo: &str,
+    dest: &PathBuf,
+    revision: Option<&str>,
+) -> Result<&'static str> {
     let python = config::expand_path(&cfg.python_path);
+    if let Some(proxy) = cfg.effective_proxy() {
+        output::verbose("Proxy", &config::redact_proxy(&proxy));
+    }
+    if let Some(token) = cfg.effective_hf_token() {
+        output::verbose("HF token", &config::redact_token(&token));
+    }
 
     // Try Python huggingface_hub first
     if python.exists() {
-        output::status("Downloading", &format!("{repo} via huggingface_hub..."));
-        let status = Command::new(python.to_string_lossy().as_ref())
-            .args([
-                "-c",
-                &format!(
-                    "from huggingface_hub import snapshot_download; snapshot_download('{}', local_dir='{}')",
-                    repo,
-                    dest.to_string_lossy()
-                ),
-            ])
-            .stdout(std::process::Stdio::inherit())
-            .stderr(std::process::Stdio::inherit())
-            .status();
+        progress::emit(Event::DownloadStarted {
+            repo,
+            source: "huggingface",
+        });
+        // Pass repo and destination through argv rather than interpolating them
+        // into the Python source, so quotes and backslashes in paths are safe.
+        let status = with_byte_progress(repo, dest, || {
+            run_with_retries(
+                cfg,
+                "huggingface_hub download",
+                || {
+                    let mut cmd = Command::new(python.to_string_lossy().as_ref());
+                    cmd.args(["-c", SNAPSHOT_DOWNLOAD_SCRIPT, repo])
+                        .arg(dest)
+                        .args(revision);
+                    cmd
+                },
+                || {},
+            )
+        });
 
         if let Ok(s) = status {
             if s.success() {
-                return Ok(());
+                return Ok("huggingface");
             }
         }
         output::warn("huggingface_hub download failed, trying git clone...");
@@ -96,11 +486,15 @@ fn download_repo(cfg: &Config, repo: &str, dest: &PathBuf) -> Result<()> {
 
     // Fallback: git clone from HuggingFace
     let url = format!("https://huggingface.co/{repo}");
-    output::status("Downloading", &format!("{repo} via git clone..."));
+    progress::emit(Event::DownloadStarted {
+        repo,
+        source: "git",
+    });
 
     // Check if git-lfs is available
     let has_lfs = Command::new("git")
         .args(["lfs", "version"])
+        .envs(cfg.network_env())
         .stdout(std::process::Stdio::null())
         .stderr(std::process::Stdio::null())
         .status()
@@ -116,25 +510,216 @@ fn download_repo(cfg: &Config, repo: &str, dest: &PathBuf) -> Result<()> {
         fs::remove_dir_all(dest).ok();
     }
 
-    let status = Command::new("git")
-        .args(["clone", "--depth", "1", &url])
-        .arg(dest.to_string_lossy().as_ref())
-        .stdout(std::process::Stdio::inherit())
-        .stderr(std::process::Stdio::inherit())
-        .status()
-        .context("failed to run git clone")?;
+    let status = with_byte_progress(repo, dest, || {
+        run_with_retries(
+            cfg,
+            "git clone",
+            || {
+                // A pinned revision may be any commit, so it needs full history
+                let mut clone = Command::new("git");
+                clone.arg("clone");
+                if revision.is_none() {
+                    clone.args(["--depth", "1"]);
+                }
+                clone.arg(&url).arg(dest);
+                clone
+            },
+            || {
+                fs::remove_dir_all(dest).ok();
+            },
+        )
+    })?;
 
     if !status.success() {
-        anyhow::bail!("git clone failed for {repo}");
+        return Err(
+            ErrorKind::Model.error(format!("git clone failed for {repo}{}", gated_hint(cfg)))
+        );
     }
 
+    if let Some(revision) = revision {
+        // With git-lfs installed, checking out fetches the revision's weights
+        let status = run_with_retries(
+            cfg,
+            "git checkout",
+            || {
+                let mut cmd = Command::new("git");
+                cmd.arg("-C")
+                    .arg(dest)
+                    .args(["checkout", "--quiet", revision]);
+                cmd
+            },
+            || {},
+        )?;
+        if !status.success() {
+            return Err(ErrorKind::Model.error(format!("revision {revision} not found in {repo}")));
+        }
+    }
+
+    let pointers = find_lfs_pointers(dest);
+    if pointers.is_empty() {
+        return Ok("git");
+    }
+
+    if has_lfs {
+        output::status(
+            "Fetching",
+            &format!("{} git-lfs object(s) via git lfs pull...", pointers.len()),
+        );
+        let status = with_byte_progress(repo, dest, || {
+            run_with_retries(
+                cfg,
+                "git lfs pull",
+                || {
+                    let mut cmd = Command::new("git");
+                    cmd.arg("-C").arg(dest).args(["lfs", "pull"]);
+                    cmd
+                },
+                || {},
+            )
+        })?;
+
+        if status.success() && find_lfs_pointers(dest).is_empty() {
+            return Ok("git");
+        }
+    }
+
+    let names: Vec<String> = pointers
+        .iter()
+        .filter_map(|p| p.strip_prefix(dest).ok())
+        .map(|p| p.to_string_lossy().to_string())
+        .collect();
+    Err(ErrorKind::Model.error(format!(
+        "model files for {repo} are git-lfs pointers, not real weights: {}\n\
+         Install git-lfs (https://git-lfs.github.com), then run `qwen-tts models update`",
+        names.join(", ")
+    )))
+}
+
+/// Git LFS pointer files are small text stubs left behind when the real
+/// objects were not fetched.
+fn is_lfs_pointer(path: &Path) -> bool {
+    const POINTER_PREFIX: &[u8] = b"version https://git-lfs";
+
+    let small = fs::metadata(path)
+        .map(|m| m.is_file() && m.len() < 1024)
+        .unwrap_or(false);
+    if !small {
+        return false;
+    }
+    fs::read(path)
+        .map(|bytes| bytes.starts_with(POINTER_PREFIX))
+        .unwrap_or(false)
+}
+
+fn find_lfs_pointers(dir: &Path) -> Vec<PathBuf> {
+    let mut found = Vec::new();
+    let entries = match fs::read_dir(dir) {
+        Ok(entries) => entries,
+        Err(_) => return found,
+    };
+    for entry in entries.flatten() {
+        let path = entry.path();
+        if path.file_name().is_some_and(|n| n == ".git") {
+            continue;
+        }
+        if path.is_dir() {
+            found.extend(find_lfs_pointers(&path));
+        } else if is_lfs_pointer(&path) {
+            found.push(path);
+        }
+    }
+    found.sort();
+    found
+}
+
+/// Human-readable install state of a variant.
+pub fn install_state(cfg: &Config, variant: &str) -> String {
+    let dir = model_dir(cfg, variant);
+    if !dir.exists() {
+        return "not installed".to_string();
+    }
+    match incomplete_reason(&dir) {
+        Some(reason) => format!("incomplete: {reason}"),
+        None => format!("installed at {}", dir.display()),
+    }
+}
+
+/// Print the repository the configured (or given) variant resolves to.
+pub fn which(variant: Option<&str>) -> Result<()> {
+    let cfg = config::load_or_default();
+    let variant = variant.unwrap_or(&cfg.model_variant);
+    let repo = repo_id(cfg.backend, variant)?;
+    println!("{} ({} backend)", variant.green(), cfg.backend);
+    if manifest_repo(cfg.backend, variant).is_some() {
+        println!(
+            "  repo:     {repo} {}",
+            "(from the models manifest)".dimmed()
+        );
+    } else {
+        println!("  repo:     {repo}");
+    }
+    if let Some(revision) = &cfg.model_revision {
+        println!("  pinned:   {revision}");
+    }
+    if let Some(installed) = read_meta(&model_dir(&cfg, variant)).and_then(|m| m.revision) {
+        println!("  revision: {installed}");
+    }
+    println!("  status:   {}", install_state(&cfg, variant));
     Ok(())
 }
 
-pub fn list() -> Result<()> {
+/// One entry of `models list --json`.
+#[derive(Debug, Serialize)]
+struct ModelEntry {
+    name: String,
+    size_bytes: u64,
+    backend: String,
+    repo: Option<String>,
+    revision: Option<String>,
+    installed_at: Option<u64>,
+    complete: bool,
+    incomplete_reason: Option<String>,
+}
+
+fn list_json(cfg: &Config, models_dir: &Path) -> Result<()> {
+    let mut entries = Vec::new();
+    if models_dir.exists() {
+        for entry in fs::read_dir(models_dir).context("failed to read models directory")? {
+            let entry = entry?;
+            if !entry.file_type()?.is_dir() {
+                continue;
+            }
+            let path = entry.path();
+            let name = entry.file_name().to_string_lossy().to_string();
+            let meta = read_meta(&path);
+            let reason = incomplete_reason(&path);
+            entries.push(ModelEntry {
+                repo: meta
+                    .as_ref()
+                    .map(|m| m.repo.clone())
+                    .or_else(|| repo_id(cfg.backend, &name).ok().map(str::to_string)),
+                revision: meta.as_ref().and_then(|m| m.revision.clone()),
+                installed_at: meta.as_ref().map(|m| m.installed_at),
+                size_bytes: dir_size(&path).unwrap_or(0),
+                backend: cfg.backend.to_string(),
+                complete: reason.is_none(),
+                incomplete_reason: reason,
+                name,
+            });
+        }
+    }
+    entries.sort_by(|a, b| a.name.cmp(&b.name));
+    output::print_json(&entries)
+}
+
+pub fn list(json: bool) -> Result<()> {
     let cfg = config::load_or_default();
     let models_dir = config::expand_path(&cfg.models_dir);
 
+    if json {
+        return list_json(&cfg, &models_dir);
+    }
+
     if !models_dir.exists() {
         println!("No models directory found at {}", models_dir.display());
         println!("Run `qwen-tts models download` to download models.");
@@ -147,11 +732,19 @@ pub fn list() -> Result<()> {
         if entry.file_type()?.is_dir() {
             let name = entry.file_name();
             let size = dir_size(&entry.path()).unwrap_or(0);
-            println!(
-                "  {} ({})",
-                name.to_string_lossy().green(),
-                human_size(size)
-            );
+            match incomplete_reason(&entry.path()) {
+                None => println!(
+                    "  {} ({})",
+                    name.to_string_lossy().green(),
+                    human_size(size)
+                ),
+                Some(reason) => println!(
+                    "  {} ({}) — {}: {reason}",
+                    name.to_string_lossy().yellow(),
+                    human_size(size),
+                    "incomplete".yellow().bold()
+                ),
+            }
             found = true;
         }
     }
@@ -164,41 +757,280 @@ pub fn list() -> Result<()> {
     Ok(())
 }
 
-pub fn download(variant: &str) -> Result<()> {
+/// Download a variant, at `revision` if given or else the configured
+/// `model_revision`.
+pub fn download(variant: &str, revision: Option<&str>) -> Result<()> {
     let cfg = config::load_or_default();
     let repo = repo_id(cfg.backend, variant)?;
     let dest = model_dir(&cfg, variant);
+    let revision = revision.or(cfg.model_revision.as_deref());
 
-    eprintln!(
-        "{} {} ({} backend)...",
-        "Downloading".cyan().bold(),
-        repo,
-        cfg.backend
+    output::status(
+        "Downloading",
+        &format!(
+            "{repo}{} ({} backend)...",
+            revision.map(|r| format!("@{r}")).unwrap_or_default(),
+            cfg.backend
+        ),
     );
 
-    fs::create_dir_all(dest.parent().unwrap())?;
-    download_repo(&cfg, repo, &dest)?;
+    config::ensure_writable(dest.parent().unwrap(), "models_dir")?;
+    let source = download_repo(&cfg, repo, &dest, revision)?;
+    write_meta(&dest, variant, repo, source, revision)?;
 
     output::success(&format!("Model '{variant}' ready at {}", dest.display()));
     Ok(())
 }
 
+/// Prints the total size of the files in the repo in argv[1], at the
+/// revision in argv[2] if given.
+const REPO_SIZE_SCRIPT: &str = "import sys; \
+    from huggingface_hub import HfApi; \
+    info = HfApi().model_info(sys.argv[1], files_metadata=True, \
+    revision=sys.argv[2] if len(sys.argv) > 2 else None); \
+    print(sum(f.size or 0 for f in info.siblings))";
+
+/// Size of `repo` on the Hub, or `None` if it can't be queried.
+fn remote_repo_size(cfg: &Config, repo: &str, revision: Option<&str>) -> Option<u64> {
+    let python = config::expand_path(&cfg.python_path);
+    let out = Command::new(python)
+        .args(["-c", REPO_SIZE_SCRIPT, repo])
+        .args(revision)
+        .envs(cfg.network_env())
+        .envs(cfg.hf_auth_env())
+        .stderr(std::process::Stdio::null())
+        .output()
+        .ok()?;
+    if !out.status.success() {
+        return None;
+    }
+    String::from_utf8_lossy(&out.stdout).trim().parse().ok()
+}
+
+/// Report how much disk downloading `variants` would use, without
+/// downloading anything. Installed variants count as nothing; sizes the
+/// Hub can't report are estimated from the parameter count.
+pub fn estimate_download(variants: &[&str], revision: Option<&str>) -> Result<()> {
+    let cfg = config::load_or_default();
+    let revision = revision.or(cfg.model_revision.as_deref());
+    output::status("Estimating", "download size from the Hugging Face Hub...");
+
+    let mut counted: Vec<&str> = Vec::new();
+    let (mut total, mut approximate, mut unknown) = (0u64, false, false);
+    for variant in variants {
+        let repo = repo_id(cfg.backend, variant)?;
+        let size = if is_model_installed(&cfg, variant) {
+            "installed".dimmed().to_string()
+        } else if counted.contains(&repo) {
+            // Several variants resolve to the same repo on some backends
+            format!("same as above ({repo})").dimmed().to_string()
+        } else {
+            counted.push(repo);
+            match remote_repo_size(&cfg, repo, revision) {
+                Some(bytes) => {
+                    total += bytes;
+                    format!("{} ({repo})", human_size(bytes))
+                }
+                None => match estimated_weight_bytes(repo) {
+                    Some(bytes) => {
+                        total += bytes;
+                        approximate = true;
+                        format!("~{} ({repo}, estimated)", human_size(bytes))
+                    }
+                    None => {
+                        unknown = true;
+                        format!("unknown ({repo})")
+                    }
+                },
+            }
+        };
+        println!("  {:<12} {size}", variant.bold());
+    }
+    println!(
+        "  {:<12} {}{}{}",
+        "total".bold(),
+        if approximate { "~" } else { "" },
+        human_size(total),
+        if unknown { " + unknown" } else { "" }
+    );
+    println!(
+        "  {:<12} {}",
+        "into".bold(),
+        config::expand_path(&cfg.models_dir).display()
+    );
+    Ok(())
+}
+
+/// Download every known variant, reporting failures together at the end.
+pub fn download_all() -> Result<()> {
+    let mut report = output::BatchReport::new("models");
+    let variants = variants();
+    for (i, variant) in variants.iter().enumerate() {
+        let (index, total) = (i + 1, variants.len());
+        progress::emit(Event::ItemStarted {
+            index,
+            total,
+            name: variant,
+        });
+        let ok = report.record(variant, download(variant, None)).is_some();
+        progress::emit(Event::ItemFinished { index, total, ok });
+    }
+    report.finish()
+}
+
+/// Install a model from a local directory or tar archive without network access.
+pub fn install_from(variant: &str, from: &str) -> Result<()> {
+    let cfg = config::load_or_default();
+    let repo = repo_id(cfg.backend, variant)?;
+    let dest = model_dir(&cfg, variant);
+    let src = config::expand_path(from);
+
+    if !src.exists() {
+        anyhow::bail!("model source not found: {}", src.display());
+    }
+
+    output::status(
+        "Installing",
+        &format!("'{variant}' from {}...", src.display()),
+    );
+
+    config::ensure_writable(dest.parent().unwrap(), "models_dir")?;
+    let staging = with_suffix(&dest, "installing");
+    if staging.exists() {
+        fs::remove_dir_all(&staging).ok();
+    }
+
+    if src.is_dir() {
+        copy_dir(&src, &staging)?;
+    } else {
+        extract_archive(&src, &staging)?;
+    }
+
+    if dest.exists() {
+        fs::remove_dir_all(&dest)
+            .with_context(|| format!("failed to remove old model at {}", dest.display()))?;
+    }
+    fs::rename(&staging, &dest)
+        .with_context(|| format!("failed to move model into {}", dest.display()))?;
+
+    let pointers = find_lfs_pointers(&dest);
+    if !pointers.is_empty() {
+        output::warn(&format!(
+            "{} file(s) in {} are git-lfs pointers, not real weights",
+            pointers.len(),
+            src.display()
+        ));
+    }
+
+    write_meta(&dest, variant, repo, &src.to_string_lossy(), None)?;
+    output::success(&format!("Model '{variant}' ready at {}", dest.display()));
+    Ok(())
+}
+
+fn copy_dir(src: &Path, dest: &Path) -> Result<()> {
+    fs::create_dir_all(dest).with_context(|| format!("failed to create {}", dest.display()))?;
+    for entry in fs::read_dir(src).with_context(|| format!("failed to read {}", src.display()))? {
+        let entry = entry?;
+        let path = entry.path();
+        if path.file_name().is_some_and(|n| n == ".git") {
+            continue;
+        }
+        let target = dest.join(entry.file_name());
+        if entry.file_type()?.is_dir() {
+            copy_dir(&path, &target)?;
+        } else {
+            fs::copy(&path, &target).with_context(|| {
+                format!("failed to copy {} → {}", path.display(), target.display())
+            })?;
+        }
+    }
+    Ok(())
+}
+
+/// `path` with `.suffix` appended to its whole file name. Unlike
+/// `with_extension`, this keeps dotted variant names such as `foo.v2` apart.
+fn with_suffix(path: &Path, suffix: &str) -> PathBuf {
+    let name = path
+        .file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .unwrap_or_default();
+    path.with_file_name(format!("{name}.{suffix}"))
+}
+
+/// Extract a tar archive (optionally gzip-compressed) into `dest`. An archive
+/// holding a single top-level directory is unwrapped so the model files end
+/// up directly in `dest`.
+fn extract_archive(archive: &Path, dest: &Path) -> Result<()> {
+    let unpack = with_suffix(dest, "unpack");
+    if unpack.exists() {
+        fs::remove_dir_all(&unpack).ok();
+    }
+    fs::create_dir_all(&unpack)
+        .with_context(|| format!("failed to create {}", unpack.display()))?;
+
+    let status = Command::new("tar")
+        .arg("-xf")
+        .arg(archive)
+        .arg("-C")
+        .arg(&unpack)
+        .stdout(std::process::Stdio::inherit())
+        .stderr(std::process::Stdio::inherit())
+        .status()
+        .context("failed to run tar")?;
+
+    if !status.success() {
+        fs::remove_dir_all(&unpack).ok();
+        anyhow::bail!("failed to extract {}", archive.display());
+    }
+
+    let entries: Vec<PathBuf> = fs::read_dir(&unpack)?
+        .filter_map(|e| e.ok())
+        .map(|e| e.path())
+        .collect();
+    let root = match entries.as_slice() {
+        [only] if only.is_dir() => only.clone(),
+        _ => unpack.clone(),
+    };
+
+    fs::rename(&root, dest).with_context(|| format!("failed to move into {}", dest.display()))?;
+    fs::remove_dir_all(&unpack).ok();
+    Ok(())
+}
+
 pub fn update(variant: Option<&str>) -> Result<()> {
     let cfg = config::load_or_default();
     let variant = variant.unwrap_or(&cfg.model_variant);
     let repo = repo_id(cfg.backend, variant)?;
     let dest = model_dir(&cfg, variant);
+    let revision = cfg.model_revision.as_deref();
 
-    if dest.exists() {
+    if let Some(revision) = revision {
+        if dest.exists() && read_meta(&dest).and_then(|m| m.revision).as_deref() == Some(revision) {
+            output::success(&format!(
+                "Model '{variant}' is already at pinned revision {revision}."
+            ));
+            return Ok(());
+        }
+        eprintln!(
+            "{} {} to pinned revision {revision}...",
+            "Updating".cyan().bold(),
+            repo
+        );
+    } else if dest.exists() {
         eprintln!("{} {} to latest version...", "Updating".cyan().bold(), repo);
         // If it's a git repo, try git pull first
         let is_git = dest.join(".git").exists();
         if is_git {
-            let status = Command::new("git")
-                .args(["-C", &dest.to_string_lossy(), "pull", "--ff-only"])
-                .stdout(std::process::Stdio::inherit())
-                .stderr(std::process::Stdio::inherit())
-                .status();
+            let status = run_with_retries(
+                &cfg,
+                "git pull",
+                || {
+                    let mut cmd = Command::new("git");
+                    cmd.arg("-C").arg(&dest).args(["pull", "--ff-only"]);
+                    cmd
+                },
+                || {},
+            );
 
             if let Ok(s) = status {
                 if s.success() {
@@ -217,46 +1049,85 @@ pub fn update(variant: Option<&str>) -> Result<()> {
     }
 
     // Full re-download
-    fs::create_dir_all(dest.parent().unwrap())?;
+    config::ensure_writable(dest.parent().unwrap(), "models_dir")?;
     if dest.exists() {
         fs::remove_dir_all(&dest).ok();
     }
-    download_repo(&cfg, repo, &dest)?;
-    output::success(&format!("Model '{variant}' updated to latest."));
+    let source = download_repo(&cfg, repo, &dest, revision)?;
+    write_meta(&dest, variant, repo, source, revision)?;
+    match revision {
+        Some(revision) => output::success(&format!("Model '{variant}' updated to {revision}.")),
+        None => output::success(&format!("Model '{variant}' updated to latest.")),
+    }
     Ok(())
 }
 
-/// Called during first-run auto-init. Prompts the user to download the default model.
-pub fn auto_download_if_needed(cfg: &Config) {
-    let variant = &cfg.model_variant;
-    if is_model_installed(cfg, variant) {
-        return;
+/// On first run, offer to download the model. In a terminal, with no
+/// download of the configured variant started yet, a menu of the backend's
+/// variants and their sizes is shown; otherwise a yes/no prompt for the
+/// configured variant. Returns the variant picked from the menu, which the
+/// caller saves as `model_variant`.
+pub fn auto_download_if_needed(cfg: &Config) -> Option<String> {
+    // A custom backend manages its own model
+    if cfg.backend == Backend::Custom || is_model_installed(cfg, &cfg.model_variant) {
+        return None;
     }
+    let repo = repo_id(cfg.backend, &cfg.model_variant).ok()?;
 
-    let repo = match repo_id(cfg.backend, variant) {
-        Ok(r) => r,
-        Err(_) => return,
-    };
+    let dest = model_dir(cfg, &cfg.model_variant);
+    let incomplete = dest.exists().then(|| incomplete_reason(&dest)).flatten();
+    let interactive = std::io::stdin().is_terminal() && std::io::stderr().is_terminal();
+    let menu = cfg.auto_download && interactive && incomplete.is_none();
+    if let Some(reason) = incomplete {
+        eprintln!(
+            "The '{}' model at {} is incomplete ({reason}).",
+            cfg.model_variant,
+            dest.display()
+        );
+    } else if menu {
+        eprintln!("No TTS model installed. Choose one to generate speech with.");
+    } else {
+        eprintln!(
+            "No TTS model installed. The '{}' model ({}) is required to generate speech.",
+            cfg.model_variant, repo
+        );
+    }
 
-    eprintln!(
-        "No TTS model installed. The '{}' model ({}) is required to generate speech.",
-        variant, repo
-    );
+    if !cfg.auto_download {
+        eprintln!("Automatic downloads are disabled. Run `qwen-tts models download` to install.");
+        eprintln!();
+        return None;
+    }
 
-    if !prompt_yn("Download it now?", true) {
+    let picked = if menu {
+        pick_variant(cfg, &suggested_variant(cfg))
+    } else {
+        output::prompt_yn("Download it now?", true).then(|| cfg.model_variant.clone())
+    };
+    let Some(variant) = picked else {
         eprintln!("Skipped. Run `qwen-tts models download` later to install.");
         eprintln!();
-        return;
+        return None;
+    };
+    let chosen = (variant != cfg.model_variant).then(|| variant.clone());
+    if is_model_installed(cfg, &variant) {
+        output::success(&format!("Model '{variant}' is already installed."));
+        eprintln!();
+        return chosen;
     }
+    let repo = repo_id(cfg.backend, &variant).ok()?;
+    let dest = model_dir(cfg, &variant);
 
     eprintln!();
-    let dest = model_dir(cfg, variant);
-    if let Err(e) = fs::create_dir_all(dest.parent().unwrap()) {
-        output::warn(&format!("Could not create models directory: {e}"));
-        return;
+    if let Err(e) = config::ensure_writable(dest.parent().unwrap(), "models_dir") {
+        output::warn(&format!("{e:#}"));
+        return chosen;
     }
 
-    match download_repo(cfg, repo, &dest) {
+    let revision = cfg.model_revision.as_deref();
+    match download_repo(cfg, repo, &dest, revision)
+        .and_then(|source| write_meta(&dest, &variant, repo, source, revision))
+    {
         Ok(()) => {
             output::success(&format!("Model '{variant}' ready."));
             eprintln!();
@@ -266,6 +1137,177 @@ pub fn auto_download_if_needed(cfg: &Config) {
             eprintln!("Run `qwen-tts models download` to try again.\n");
         }
     }
+    chosen
+}
+
+/// What a built-in variant is for, as shown in the first-run menu.
+fn variant_summary(variant: &str) -> String {
+    let kind = match variant.trim_end_matches("-4bit") {
+        "base" => "Base: speech and voice cloning",
+        "custom" => "CustomVoice: preset speakers",
+        "design" => "VoiceDesign: voices from descriptions",
+        _ => return "from the models manifest".to_string(),
+    };
+    if variant.ends_with("-4bit") {
+        format!("{kind}, 4-bit")
+    } else {
+        kind.to_string()
+    }
+}
+
+/// Variants offered in the first-run menu: one per repository, since on
+/// the CUDA/CPU backends several names load the same model. `default`
+/// stands for its repository when it shares one.
+fn menu_variants(backend: Backend, default: &str) -> Vec<&'static str> {
+    let all = variants();
+    let default_repo = all
+        .contains(&default)
+        .then(|| repo_id(backend, default).ok())
+        .flatten();
+    let mut seen = HashSet::new();
+    all.into_iter()
+        .filter(|v| match repo_id(backend, v) {
+            Ok(repo) if Some(repo) == default_repo && *v != default => false,
+            Ok(repo) => seen.insert(repo),
+            Err(_) => false,
+        })
+        .collect()
+}
+
+/// Ask which variant to download, listing each with its download size.
+/// Enter picks `default`; `None` if the user skips.
+fn pick_variant(cfg: &Config, default: &str) -> Option<String> {
+    let variants = menu_variants(cfg.backend, default);
+    let default_index = variants.iter().position(|v| *v == default).unwrap_or(0);
+    output::status("Checking", "model sizes on the Hugging Face Hub...");
+
+    eprintln!();
+    for (i, variant) in variants.iter().enumerate() {
+        let size = match repo_id(cfg.backend, variant) {
+            Ok(repo) => match remote_repo_size(cfg, repo, cfg.model_revision.as_deref()) {
+                Some(bytes) => human_size(bytes),
+                None => estimated_weight_bytes(repo)
+                    .map_or_else(|| "?".to_string(), |b| format!("~{}", human_size(b))),
+            },
+            Err(_) => "?".to_string(),
+        };
+        let marker = if i == default_index { " (default)" } else { "" };
+        eprintln!(
+            "  {:>2}) {:<12} {:>10}  {}{}",
+            i + 1,
+            variant.bold(),
+            size,
+            variant_summary(variant),
+            marker.dimmed()
+        );
+    }
+    eprintln!("  {:>2}) skip for now", 0);
+
+    loop {
+        eprint!("Model [{}]: ", default_index + 1);
+        std::io::stderr().flush().ok();
+        let mut input = String::new();
+        if std::io::stdin().read_line(&mut input).unwrap_or(0) == 0 {
+            return Some(variants[default_index].to_string());
+        }
+        let input = input.trim();
+        if input.is_empty() {
+            return Some(variants[default_index].to_string());
+        }
+        if input == "0" || input.eq_ignore_ascii_case("skip") {
+            return None;
+        }
+        let choice = match input.parse::<usize>() {
+            Ok(n) => n.checked_sub(1).and_then(|i| variants.get(i)),
+            Err(_) => variants.iter().find(|v| **v == input),
+        };
+        match choice {
+            Some(variant) => return Some(variant.to_string()),
+            None => eprintln!(
+                "Enter a number from 0 to {}, or a variant name.",
+                variants.len()
+            ),
+        }
+    }
+}
+
+/// Find directories in `models_dir` that are not a usable model: names
+/// that match no variant, or variants that are incomplete. Lists them with
+/// their size and removes them after confirmation (or with `yes`).
+pub fn gc(yes: bool, dry_run: bool) -> Result<()> {
+    let cfg = config::load_or_default();
+    let models_dir = config::expand_path(&cfg.models_dir);
+    let entries = match fs::read_dir(&models_dir) {
+        Ok(entries) => entries,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+            println!("No models directory found at {}", models_dir.display());
+            return Ok(());
+        }
+        Err(e) => return Err(e).context("failed to read models directory"),
+    };
+
+    let mut orphans = Vec::new();
+    for entry in entries.flatten() {
+        let path = entry.path();
+        if !path.is_dir() {
+            continue;
+        }
+        let name = entry.file_name().to_string_lossy().to_string();
+        let reason = if repo_id(cfg.backend, &name).is_err() {
+            "not a known model variant".to_string()
+        } else if let Some(reason) = incomplete_reason(&path) {
+            format!("incomplete ({reason})")
+        } else {
+            continue;
+        };
+        let size = dir_size(&path).unwrap_or(0);
+        orphans.push((name, path, reason, size));
+    }
+    orphans.sort();
+
+    if orphans.is_empty() {
+        output::success("No orphaned model directories.");
+        return Ok(());
+    }
+
+    let total: u64 = orphans.iter().map(|(_, _, _, size)| size).sum();
+    for (name, _, reason, size) in &orphans {
+        println!("  {} ({}) — {reason}", name.yellow(), human_size(*size));
+    }
+    if dry_run {
+        println!(
+            "{} director{} ({}) would be removed.",
+            orphans.len(),
+            if orphans.len() == 1 { "y" } else { "ies" },
+            human_size(total)
+        );
+        return Ok(());
+    }
+    if !yes {
+        if !std::io::stdin().is_terminal() {
+            return Err(ErrorKind::Config
+                .error("refusing to remove model directories without confirmation; pass --yes"));
+        }
+        // A download still in progress also looks incomplete
+        let question = format!(
+            "Remove {} (make sure no download is running)?",
+            human_size(total)
+        );
+        if !output::prompt_yn(&question, false) {
+            println!("Nothing removed.");
+            return Ok(());
+        }
+    }
+
+    let mut reclaimed = 0;
+    for (name, path, _, size) in &orphans {
+        match fs::remove_dir_all(path) {
+            Ok(()) => reclaimed += size,
+            Err(e) => output::warn(&format!("could not remove {name}: {e}")),
+        }
+    }
+    output::success(&format!("Reclaimed {}.", human_size(reclaimed)));
+    Ok(())
 }
 
 fn dir_size(path: &Path) -> Result<u64> {
@@ -282,7 +1324,7 @@ fn dir_size(path: &Path) -> Result<u64> {
     Ok(total)
 }
 
-fn human_size(bytes: u64) -> String {
+pub(crate) fn human_size(bytes: u64) -> String {
     const KB: u64 = 1024;
     const MB: u64 = KB * 1024;
     const GB: u64 = MB * 1024;
@@ -297,3 +1339,69 @@ fn human_size(bytes: u64) -> String {
         format!("{bytes} B")
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn quantized_variants() {
+        assert_eq!(
+            quantized_variant(Backend::Mlx, "base").as_deref(),
+            Some("base-4bit")
+        );
+        assert_eq!(quantized_variant(Backend::Mlx, "base-4bit"), None);
+        // Every built-in CUDA variant is the same full-precision model
+        assert_eq!(quantized_variant(Backend::Cuda, "base"), None);
+        assert_eq!(fitting_variant(Backend::Cuda, "design", 1024), None);
+    }
+
+    #[test]
+    fn menu_lists_each_repository_once() {
+        assert_eq!(menu_variants(Backend::Mlx, "base"), VARIANTS);
+        assert_eq!(menu_variants(Backend::Cuda, "base"), ["base"]);
+        assert_eq!(menu_variants(Backend::Cpu, "design"), ["design"]);
+        assert_eq!(menu_variants(Backend::Cpu, "pro"), ["base"]);
+    }
+
+    #[test]
+    fn variant_names() {
+        for name in ["base", "base-4bit", "foo.v2", "0.6b_x"] {
+            assert!(is_valid_variant_name(name), "{name}");
+        }
+        for name in [
+            "", "../../x", "..", ".hidden", "-x", "Base", "a/b", "a\\b", "a b",
+        ] {
+            assert!(!is_valid_variant_name(name), "{name}");
+        }
+    }
+
+    #[test]
+    fn meta_moves_off_the_project_config_name() {
+        let dir = std::env::temp_dir().join(format!("qwen-tts-meta-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(
+            dir.join(LEGACY_META_FILE),
+            "variant = \"base\"\nrepo = \"a/b\"\nsource = \"git\"\ninstalled_at = 1\n",
+        )
+        .unwrap();
+        assert_eq!(read_meta(&dir).unwrap().repo, "a/b");
+
+        write_meta(&dir, "base", "c/d", "huggingface", None).unwrap();
+        assert!(!dir.join(LEGACY_META_FILE).exists());
+        assert_eq!(read_meta(&dir).unwrap().repo, "c/d");
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn staging_dirs_keep_dotted_names() {
+        assert_eq!(
+            with_suffix(Path::new("/m/foo.v2"), "installing"),
+            Path::new("/m/foo.v2.installing")
+        );
+        assert_eq!(
+            with_suffix(Path::new("/m/base"), "unpack"),
+            Path::new("/m/base.unpack")
+        );
+    }
+}
@@ -0,0 +1,1111 @@
+//! Text normalization before synthesis: spelling out abbreviations, units,
+//! and numbers that the model would otherwise read inconsistently.
+
+use std::fmt;
+
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+
+use crate::config::Config;
+use crate::error::ErrorKind;
+
+/// A class of abbreviations `--expand` can spell out.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Expansion {
+    /// Every class below
+    All,
+    /// Dr. → Doctor, Mr. → Mister, Prof. → Professor, ...
+    Titles,
+    /// St. → Street or Saint, Ave. → Avenue, Rd. → Road, ...
+    Addresses,
+    /// e.g. → for example, etc. → et cetera, vs. → versus, ...
+    Common,
+    /// km → kilometers, kg → kilograms, % → percent, after a number
+    Units,
+    /// Numbers, currencies, and dates, even with `normalize_numbers` off
+    Numbers,
+}
+
+impl Expansion {
+    const NAMES: &'static [&'static str] =
+        &["all", "titles", "addresses", "common", "units", "numbers"];
+}
+
+impl fmt::Display for Expansion {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Expansion::All => "all",
+            Expansion::Titles => "titles",
+            Expansion::Addresses => "addresses",
+            Expansion::Common => "common",
+            Expansion::Units => "units",
+            Expansion::Numbers => "numbers",
+        };
+        write!(f, "{name}")
+    }
+}
+
+impl std::str::FromStr for Expansion {
+    type Err = anyhow::Error;
+    fn from_str(s: &str) -> Result<Self> {
+        match s.trim().to_lowercase().as_str() {
+            "all" => Ok(Expansion::All),
+            "titles" => Ok(Expansion::Titles),
+            "addresses" => Ok(Expansion::Addresses),
+            "common" => Ok(Expansion::Common),
+            "units" => Ok(Expansion::Units),
+            "numbers" => Ok(Expansion::Numbers),
+            _ => Err(ErrorKind::Config.error(format!(
+                "unknown expansion: {s} (expected {})",
+                Expansion::NAMES.join(", ")
+            ))),
+        }
+    }
+}
+
+/// Conventions for speaking numbers and dates.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Locale {
+    /// Month/day/year dates, "one hundred five"
+    #[default]
+    #[serde(rename = "en-US")]
+    EnUs,
+    /// Day/month/year dates, "one hundred and five"
+    #[serde(rename = "en-GB")]
+    EnGb,
+}
+
+impl fmt::Display for Locale {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Locale::EnUs => write!(f, "en-US"),
+            Locale::EnGb => write!(f, "en-GB"),
+        }
+    }
+}
+
+impl std::str::FromStr for Locale {
+    type Err = anyhow::Error;
+    fn from_str(s: &str) -> Result<Self> {
+        match s.to_lowercase().replace('_', "-").as_str() {
+            "en-us" => Ok(Locale::EnUs),
+            "en-gb" | "en-uk" => Ok(Locale::EnGb),
+            _ => Err(ErrorKind::Config.error(format!(
+                "unknown number locale: {s} (expected en-US or en-GB)"
+            ))),
+        }
+    }
+}
+
+/// Normalization applied to input text before it reaches the backend.
+#[derive(Debug, Clone, Default)]
+pub struct Options {
+    /// Abbreviation classes to spell out (`--expand`)
+    pub expand: Vec<Expansion>,
+    /// Leave numerals as written (`--no-normalize-numbers`)
+    pub keep_numbers: bool,
+    /// Locale numbers, currencies, and dates are spoken in; `None` leaves
+    /// them alone. Filled in from the config by `with_defaults`.
+    pub numbers: Option<Locale>,
+}
+
+impl Options {
+    /// Fill in number normalization from `normalize_numbers` and
+    /// `number_locale`; `--expand numbers` turns it on regardless.
+    pub fn with_defaults(&self, cfg: &Config) -> Self {
+        let numbers =
+            !self.keep_numbers && (cfg.normalize_numbers || self.expand_class(Expansion::Numbers));
+        Self {
+            numbers: numbers.then_some(cfg.number_locale),
+            ..self.clone()
+        }
+    }
+
+    fn expand_class(&self, class: Expansion) -> bool {
+        self.expand.contains(&class) || self.expand.contains(&Expansion::All)
+    }
+
+    /// Rewrite `text` for speech: abbreviations first, then numbers.
+    /// Whitespace and punctuation around each word are kept as they are.
+    pub fn apply(&self, text: &str) -> String {
+        let mut text = text.to_string();
+        if !self.expand.is_empty() {
+            text = rewrite_words(&text, |core, prev, next| self.expand_core(core, prev, next));
+        }
+        if let Some(locale) = self.numbers {
+            text = rewrite_words(&text, |core, prev, next| {
+                speak_number(core, prev, next, locale)
+            });
+        }
+        text
+    }
+
+    fn expand_core(&self, core: &str, prev: Option<&str>, next: Option<&str>) -> Option<String> {
+        if self.expand_class(Expansion::Addresses) {
+            match core {
+                "St." if is_saint(prev, next) => return Some("Saint".into()),
+                "St." => return Some("Street".into()),
+                "Dr." if is_street_suffix(prev, next) => return Some("Drive".into()),
+                _ => {}
+            }
+            if let Some(spoken) = lookup(ADDRESSES, core) {
+                return Some(spoken.into());
+            }
+        }
+        if self.expand_class(Expansion::Titles) {
+            if let Some(spoken) = lookup(TITLES, core) {
+                return Some(spoken.into());
+            }
+        }
+        if self.expand_class(Expansion::Common) {
+            if let Some(spoken) = lookup(COMMON, core) {
+                // "etc." often ends the sentence too
+                let ends = core == "etc." && next.is_none_or(starts_upper);
+                return Some(if ends {
+                    format!("{spoken}.")
+                } else {
+                    spoken.into()
+                });
+            }
+        }
+        if self.expand_class(Expansion::Units) {
+            return expand_unit(core, prev);
+        }
+        None
+    }
+}
+
+/// Apply `rewrite` to the core of every word (see `split_affixes`), with
+/// the cores of its neighbors as context.
+fn rewrite_words(
+    text: &str,
+    rewrite: impl Fn(&str, Option<&str>, Option<&str>) -> Option<String>,
+) -> String {
+    let body = text.trim_start();
+    let mut out = String::with_capacity(text.len());
+    out.push_str(&text[..text.len() - body.len()]);
+
+    let words = split_words(body);
+    for (i, (word, space)) in words.iter().enumerate() {
+        let prev = i.checked_sub(1).map(|j| core_of(words[j].0));
+        let next = words.get(i + 1).map(|(w, _)| core_of(w));
+        let (lead, core, trail) = split_affixes(word);
+        if let Some(mut spoken) = rewrite(core, prev, next) {
+            // The abbreviation's period also ended the text
+            if next.is_none() && core.ends_with('.') && !spoken.ends_with('.') {
+                spoken.push('.');
+            }
+            out.push_str(&format!("{lead}{spoken}{trail}"));
+        } else if let Some(spoken) = core
+            .strip_suffix('.')
+            .and_then(|stripped| rewrite(stripped, prev, next))
+        {
+            // A sentence-final period isn't part of the word
+            out.push_str(&format!("{lead}{spoken}.{trail}"));
+        } else {
+            out.push_str(word);
+        }
+        out.push_str(space);
+    }
+    out
+}
+
+/// A unit after a number ("5 km"), or attached to one ("5km", "50%").
+fn expand_unit(core: &str, prev: Option<&str>) -> Option<String> {
+    if let Some(count) = prev.filter(|p| is_number(p)) {
+        return unit_words(core, count).map(String::from);
+    }
+    let split = core.find(|c: char| !(c.is_ascii_digit() || c == '.' || c == ','))?;
+    let (count, unit) = core.split_at(split);
+    if !is_number(count) {
+        return None;
+    }
+    Some(format!("{count} {}", unit_words(unit, count)?))
+}
+
+const TITLES: &[(&str, &str)] = &[
+    ("Mr.", "Mister"),
+    ("Mrs.", "Missus"),
+    ("Ms.", "Miz"),
+    ("Dr.", "Doctor"),
+    ("Prof.", "Professor"),
+    ("Sr.", "Senior"),
+    ("Jr.", "Junior"),
+    ("Rev.", "Reverend"),
+    ("Gen.", "General"),
+    ("Gov.", "Governor"),
+    ("Sen.", "Senator"),
+    ("Rep.", "Representative"),
+    ("Capt.", "Captain"),
+    ("Lt.", "Lieutenant"),
+    ("Sgt.", "Sergeant"),
+];
+
+const ADDRESSES: &[(&str, &str)] = &[
+    ("Ave.", "Avenue"),
+    ("Rd.", "Road"),
+    ("Blvd.", "Boulevard"),
+    ("Ln.", "Lane"),
+    ("Ct.", "Court"),
+    ("Pl.", "Place"),
+    ("Hwy.", "Highway"),
+    ("Mt.", "Mount"),
+    ("Ft.", "Fort"),
+    ("Apt.", "Apartment"),
+    ("Ste.", "Suite"),
+];
+
+const COMMON: &[(&str, &str)] = &[
+    ("e.g.", "for example"),
+    ("E.g.", "For example"),
+    ("i.e.", "that is"),
+    ("I.e.", "That is"),
+    ("etc.", "et cetera"),
+    ("vs.", "versus"),
+    ("vs", "versus"),
+    ("approx.", "approximately"),
+    ("Approx.", "Approximately"),
+    ("incl.", "including"),
+    ("dept.", "department"),
+    ("Dept.", "Department"),
+    ("est.", "established"),
+    ("Est.", "Established"),
+];
+
+/// Unit symbols with their singular and plural spoken forms.
+const UNITS: &[(&str, &str, &str)] = &[
+    ("%", "percent", "percent"),
+    ("km", "kilometer", "kilometers"),
+    ("m", "meter", "meters"),
+    ("cm", "centimeter", "centimeters"),
+    ("mm", "millimeter", "millimeters"),
+    ("mi", "mile", "miles"),
+    ("ft", "foot", "feet"),
+    ("kg", "kilogram", "kilograms"),
+    ("g", "gram", "grams"),
+    ("mg", "milligram", "milligrams"),
+    ("lb", "pound", "pounds"),
+    ("lbs", "pounds", "pounds"),
+    ("oz", "ounce", "ounces"),
+    ("l", "liter", "liters"),
+    ("L", "liter", "liters"),
+    ("ml", "milliliter", "milliliters"),
+    ("mL", "milliliter", "milliliters"),
+    ("mph", "mile per hour", "miles per hour"),
+    ("km/h", "kilometer per hour", "kilometers per hour"),
+    ("kph", "kilometer per hour", "kilometers per hour"),
+    ("sec", "second", "seconds"),
+    ("min", "minute", "minutes"),
+    ("hr", "hour", "hours"),
+    ("hrs", "hours", "hours"),
+    ("KB", "kilobyte", "kilobytes"),
+    ("MB", "megabyte", "megabytes"),
+    ("GB", "gigabyte", "gigabytes"),
+    ("TB", "terabyte", "terabytes"),
+    ("Hz", "hertz", "hertz"),
+    ("kHz", "kilohertz", "kilohertz"),
+    ("MHz", "megahertz", "megahertz"),
+    ("GHz", "gigahertz", "gigahertz"),
+    ("°C", "degree Celsius", "degrees Celsius"),
+    ("°F", "degree Fahrenheit", "degrees Fahrenheit"),
+];
+
+fn lookup(table: &[(&str, &'static str)], core: &str) -> Option<&'static str> {
+    table
+        .iter()
+        .find(|(short, _)| *short == core)
+        .map(|(_, long)| *long)
+}
+
+fn unit_words(unit: &str, count: &str) -> Option<&'static str> {
+    let (_, one, many) = UNITS.iter().find(|(symbol, _, _)| *symbol == unit)?;
+    Some(if count == "1" { one } else { many })
+}
+
+/// Words with the whitespace that follows each.
+fn split_words(text: &str) -> Vec<(&str, &str)> {
+    let mut words = Vec::new();
+    let mut rest = text;
+    while !rest.is_empty() {
+        let word_end = rest.find(char::is_whitespace).unwrap_or(rest.len());
+        let (word, after) = rest.split_at(word_end);
+        let space_end = after
+            .find(|c: char| !c.is_whitespace())
+            .unwrap_or(after.len());
+        let (space, next) = after.split_at(space_end);
+        words.push((word, space));
+        rest = next;
+    }
+    words
+}
+
+/// Opening quotes and brackets before a word, and closing punctuation
+/// after it. Periods stay with the word, since they mark abbreviations.
+fn split_affixes(word: &str) -> (&str, &str, &str) {
+    let lead_len = word.len()
+        - word
+            .trim_start_matches(|c: char| "\"'([{“‘«".contains(c))
+            .len();
+    let (lead, rest) = word.split_at(lead_len);
+    let core_len = rest
+        .trim_end_matches(|c: char| ",;:!?)]}\"'”’»".contains(c))
+        .len();
+    let (core, trail) = rest.split_at(core_len);
+    (lead, core, trail)
+}
+
+fn core_of(word: &str) -> &str {
+    split_affixes(word).1
+}
+
+fn starts_upper(word: &str) -> bool {
+    word.chars().next().is_some_and(char::is_uppercase)
+}
+
+/// "St. Louis" is a saint; "Main St." and "12 St." are streets.
+fn is_saint(prev: Option<&str>, next: Option<&str>) -> bool {
+    next.is_some_and(starts_upper) && !prev.is_some_and(|p| starts_upper(p) || is_number(p))
+}
+
+/// "Elm Dr. is closed" is a street; "Dr. Smith" is a doctor.
+fn is_street_suffix(prev: Option<&str>, next: Option<&str>) -> bool {
+    prev.is_some_and(|p| starts_upper(p) && !p.ends_with('.')) && !next.is_some_and(starts_upper)
+}
+
+/// Digits with optional thousands separators and decimals, e.g. "1,250.5".
+fn is_number(word: &str) -> bool {
+    let (int, frac) = match word.split_once('.') {
+        Some((_, "")) => return false,
+        Some(parts) => parts,
+        None => (word, ""),
+    };
+    let groups_ok = match int.split_once(',') {
+        None => true,
+        Some((head, _)) => {
+            (1..=3).contains(&head.len()) && int.split(',').skip(1).all(|g| g.len() == 3)
+        }
+    };
+    let digits = |s: &str| s.chars().all(|c| c.is_ascii_digit() || c == ',');
+    !int.is_empty()
+        && int.starts_with(|c: char| c.is_ascii_digit())
+        && groups_ok
+        && digits(int)
+        && frac.chars().all(|c| c.is_ascii_digit())
+}
+
+/// Spoken form of a number-like word, trying the more specific readings
+/// first. `None` leaves the word as written.
+fn speak_number(
+    core: &str,
+    prev: Option<&str>,
+    next: Option<&str>,
+    locale: Locale,
+) -> Option<String> {
+    if let Some(rest) = core.strip_prefix(['-', '−']) {
+        if rest.starts_with(|c: char| {
+            c.is_ascii_digit() || CURRENCIES.iter().any(|cur| rest.starts_with(cur.symbol))
+        }) {
+            return Some(format!("minus {}", speak_number(rest, prev, next, locale)?));
+        }
+        return None;
+    }
+    currency(core, locale)
+        .or_else(|| date(core, locale))
+        .or_else(|| fraction(core, locale))
+        .or_else(|| ordinal_number(core, locale))
+        .or_else(|| decade(core, locale))
+        .or_else(|| percent(core, locale))
+        .or_else(|| day_of_month(core, prev, next, locale))
+        .or_else(|| year(core, prev, next, locale))
+        .or_else(|| number_words(core, locale))
+}
+
+/// Plain number such as "42", "1,000", or "3.14".
+fn number_words(word: &str, locale: Locale) -> Option<String> {
+    if !is_number(word) {
+        return None;
+    }
+    let (int, frac) = word.split_once('.').unwrap_or((word, ""));
+    let int = int.replace(',', "");
+    // Leading zeros are read digit by digit, like "007"
+    let mut spoken = if int.len() > 1 && int.starts_with('0') {
+        digit_words(&int)
+    } else {
+        cardinal(int.parse().ok()?, locale)
+    };
+    if !frac.is_empty() {
+        spoken = format!("{spoken} point {}", digit_words(frac));
+    }
+    Some(spoken)
+}
+
+/// Integer without separators or leading zeros, e.g. "42".
+fn plain_integer(word: &str) -> Option<u64> {
+    let valid = !word.is_empty()
+        && word.chars().all(|c| c.is_ascii_digit())
+        && (word == "0" || !word.starts_with('0'));
+    valid.then(|| word.parse().ok()).flatten()
+}
+
+struct Currency {
+    symbol: char,
+    one: &'static str,
+    many: &'static str,
+    /// Hundredth unit, if amounts have one
+    minor: Option<(&'static str, &'static str)>,
+}
+
+const CURRENCIES: &[Currency] = &[
+    Currency {
+        symbol: '$',
+        one: "dollar",
+        many: "dollars",
+        minor: Some(("cent", "cents")),
+    },
+    Currency {
+        symbol: '€',
+        one: "euro",
+        many: "euros",
+        minor: Some(("cent", "cents")),
+    },
+    Currency {
+        symbol: '£',
+        one: "pound",
+        many: "pounds",
+        minor: Some(("penny", "pence")),
+    },
+    Currency {
+        symbol: '¥',
+        one: "yen",
+        many: "yen",
+        minor: None,
+    },
+];
+
+/// Suffixes for large amounts, as in "$2.5M" or "£3bn".
+const SCALE_SUFFIXES: &[(&str, &str)] = &[
+    ("k", "thousand"),
+    ("K", "thousand"),
+    ("M", "million"),
+    ("m", "million"),
+    ("mn", "million"),
+    ("B", "billion"),
+    ("bn", "billion"),
+    ("T", "trillion"),
+    ("tn", "trillion"),
+];
+
+/// "$5" → "five dollars", "$3.50" → "three dollars and fifty cents",
+/// "$1.5M" → "one point five million dollars".
+fn currency(core: &str, locale: Locale) -> Option<String> {
+    let currency = CURRENCIES.iter().find(|c| core.starts_with(c.symbol))?;
+    let amount = &core[currency.symbol.len_utf8()..];
+    let split = amount
+        .find(|c: char| !(c.is_ascii_digit() || c == '.' || c == ','))
+        .unwrap_or(amount.len());
+    let (amount, suffix) = amount.split_at(split);
+    if !is_number(amount) {
+        return None;
+    }
+    if !suffix.is_empty() {
+        let (_, scale) = SCALE_SUFFIXES.iter().find(|(s, _)| *s == suffix)?;
+        let count = number_words(amount, locale)?;
+        return Some(format!("{count} {scale} {}", currency.many));
+    }
+
+    let (int, frac) = amount.split_once('.').unwrap_or((amount, ""));
+    let major: u64 = int.replace(',', "").parse().ok()?;
+    let minor = match (frac.len(), currency.minor) {
+        (0, _) => None,
+        (1 | 2, Some(names)) => Some((format!("{frac:0<2}").parse::<u64>().ok()?, names)),
+        // Fractions of the smallest unit are read as a decimal amount
+        _ => {
+            return Some(format!(
+                "{} {}",
+                number_words(amount, locale)?,
+                currency.many
+            ))
+        }
+    };
+    let unit = if major == 1 {
+        currency.one
+    } else {
+        currency.many
+    };
+    let major_words = format!("{} {unit}", cardinal(major, locale));
+    Some(match minor {
+        Some((0, _)) | None => major_words,
+        Some((cents, (one, many))) => {
+            let cents_words = format!(
+                "{} {}",
+                cardinal(cents, locale),
+                if cents == 1 { one } else { many }
+            );
+            if major == 0 {
+                cents_words
+            } else {
+                format!("{major_words} and {cents_words}")
+            }
+        }
+    })
+}
+
+const MONTHS: [&str; 12] = [
+    "January",
+    "February",
+    "March",
+    "April",
+    "May",
+    "June",
+    "July",
+    "August",
+    "September",
+    "October",
+    "November",
+    "December",
+];
+
+/// "2024-03-04", or "3/4/2024" read as month/day in en-US and day/month
+/// in en-GB.
+fn date(core: &str, locale: Locale) -> Option<String> {
+    let parts: Vec<&str> = core.split(['-', '/']).collect();
+    let [a, b, c] = parts.as_slice() else {
+        return None;
+    };
+    let number = |s: &str, len: std::ops::RangeInclusive<usize>| {
+        (len.contains(&s.len()) && s.chars().all(|c| c.is_ascii_digit()))
+            .then(|| s.parse::<u32>().ok())
+            .flatten()
+    };
+    let (year, month, day) = if core.contains('-') {
+        (number(a, 4..=4)?, number(b, 2..=2)?, number(c, 2..=2)?)
+    } else {
+        let (first, second) = (number(a, 1..=2)?, number(b, 1..=2)?);
+        match locale {
+            Locale::EnUs => (number(c, 4..=4)?, first, second),
+            Locale::EnGb => (number(c, 4..=4)?, second, first),
+        }
+    };
+    if !(1..=12).contains(&month) || !(1..=31).contains(&day) {
+        return None;
+    }
+    Some(spoken_date(year, month, day, locale))
+}
+
+fn spoken_date(year: u32, month: u32, day: u32, locale: Locale) -> String {
+    let month = MONTHS[month as usize - 1];
+    let day = ordinal(u64::from(day), locale);
+    let year = year_words(year, locale);
+    match locale {
+        Locale::EnUs => format!("{month} {day}, {year}"),
+        Locale::EnGb => format!("the {day} of {month} {year}"),
+    }
+}
+
+/// A day next to a month name: "March 4" → "March fourth", and
+/// "4 March" → "the fourth of March".
+fn day_of_month(
+    core: &str,
+    prev: Option<&str>,
+    next: Option<&str>,
+    locale: Locale,
+) -> Option<String> {
+    let day = plain_integer(core).filter(|d| (1..=31).contains(d))?;
+    let is_month = |word: &str| MONTHS.contains(&word.trim_end_matches(','));
+    if prev.is_some_and(is_month) {
+        return Some(ordinal(day, locale));
+    }
+    if next.is_some_and(is_month) {
+        return Some(format!("the {} of", ordinal(day, locale)));
+    }
+    None
+}
+
+/// Proper fractions with a common denominator: "1/2" → "one half",
+/// "3/4" → "three quarters". Others, such as "12/25" (maybe a date) or
+/// "24/7", are left alone.
+fn fraction(core: &str, locale: Locale) -> Option<String> {
+    let (num, den) = core.split_once('/')?;
+    let (num, den) = (plain_integer(num)?, plain_integer(den)?);
+    if num == 0 || num >= den || !matches!(den, 2..=10 | 16 | 32 | 64 | 100) {
+        return None;
+    }
+    let (one, many) = match den {
+        2 => ("half".to_string(), "halves".to_string()),
+        4 => ("quarter".to_string(), "quarters".to_string()),
+        _ => {
+            let one = ordinal(den, locale);
+            let many = format!("{one}s");
+            (one, many)
+        }
+    };
+    let name = if num == 1 { one } else { many };
+    Some(format!("{} {name}", cardinal(num, locale)))
+}
+
+/// "1st" → "first", "23rd" → "twenty-third".
+fn ordinal_number(core: &str, locale: Locale) -> Option<String> {
+    let split = core.find(|c: char| !c.is_ascii_digit())?;
+    let (digits, suffix) = core.split_at(split);
+    if !matches!(suffix, "st" | "nd" | "rd" | "th") {
+        return None;
+    }
+    Some(ordinal(plain_integer(digits)?, locale))
+}
+
+/// "1990s" → "nineteen nineties".
+fn decade(core: &str, locale: Locale) -> Option<String> {
+    let year = plain_integer(core.strip_suffix('s')?).filter(|y| is_year(*y) && y % 10 == 0)?;
+    let words = year_words(year as u32, locale);
+    Some(match words.strip_suffix('y') {
+        Some(stem) => format!("{stem}ies"),
+        None => format!("{words}s"),
+    })
+}
+
+/// "50%" → "fifty percent".
+fn percent(core: &str, locale: Locale) -> Option<String> {
+    Some(format!(
+        "{} percent",
+        number_words(core.strip_suffix('%')?, locale)?
+    ))
+}
+
+/// Four-digit numbers without separators in this range are read as years.
+fn is_year(n: u64) -> bool {
+    (1100..=2099).contains(&n)
+}
+
+/// Words before a number that mark it as a year, as in "in 1999".
+const YEAR_CUES: &[&str] = &[
+    "in", "since", "until", "till", "during", "circa", "c.", "ca.", "year", "spring", "summer",
+    "autumn", "fall", "winter",
+];
+
+/// Words after a number that mark it as a year, as in "1492 AD".
+const ERAS: &[&str] = &["AD", "BC", "BCE", "CE"];
+
+/// "1999" → "nineteen ninety-nine", "2024" → "twenty twenty-four", when
+/// the neighbors show it is a year ("in 1999", "March 4, 2024",
+/// "1492 AD"); "1500 apples" stays a count.
+fn year(core: &str, prev: Option<&str>, next: Option<&str>, locale: Locale) -> Option<String> {
+    let year = plain_integer(core).filter(|y| core.len() == 4 && is_year(*y))?;
+    let cued = prev.is_some_and(|p| {
+        YEAR_CUES.contains(&p.to_lowercase().as_str())
+            || MONTHS.contains(&p)
+            // The day of "March 4, 2024" or "4th"
+            || plain_integer(p).is_some_and(|d| (1..=31).contains(&d))
+            || ordinal_number(p, locale).is_some()
+    }) || next.is_some_and(|n| ERAS.contains(&n.trim_end_matches('.')));
+    cued.then(|| year_words(year as u32, locale))
+}
+
+fn year_words(year: u32, locale: Locale) -> String {
+    let (century, rest) = (u64::from(year / 100), u64::from(year % 100));
+    match (century, rest) {
+        // 2000 to 2009 are "two thousand (and) five"
+        (20, 0..=9) => cardinal(u64::from(year), locale),
+        (_, 0) => format!("{} hundred", cardinal(century, locale)),
+        (_, 1..=9) => format!("{} oh {}", cardinal(century, locale), ONES[rest as usize]),
+        _ => format!("{} {}", cardinal(century, locale), cardinal(rest, locale)),
+    }
+}
+
+/// English ordinal for `n`, e.g. 21 → "twenty-first".
+fn ordinal(n: u64, locale: Locale) -> String {
+    let words = cardinal(n, locale);
+    let split = words.rfind([' ', '-']).map_or(0, |i| i + 1);
+    let (head, last) = words.split_at(split);
+    let last = match last {
+        "one" => "first".to_string(),
+        "two" => "second".to_string(),
+        "three" => "third".to_string(),
+        "five" => "fifth".to_string(),
+        "eight" => "eighth".to_string(),
+        "nine" => "ninth".to_string(),
+        "twelve" => "twelfth".to_string(),
+        _ => match last.strip_suffix('y') {
+            Some(stem) => format!("{stem}ieth"),
+            None => format!("{last}th"),
+        },
+    };
+    format!("{head}{last}")
+}
+
+const ONES: [&str; 20] = [
+    "zero",
+    "one",
+    "two",
+    "three",
+    "four",
+    "five",
+    "six",
+    "seven",
+    "eight",
+    "nine",
+    "ten",
+    "eleven",
+    "twelve",
+    "thirteen",
+    "fourteen",
+    "fifteen",
+    "sixteen",
+    "seventeen",
+    "eighteen",
+    "nineteen",
+];
+
+const TENS: [&str; 10] = [
+    "", "", "twenty", "thirty", "forty", "fifty", "sixty", "seventy", "eighty", "ninety",
+];
+
+const SCALES: [&str; 7] = [
+    "",
+    "thousand",
+    "million",
+    "billion",
+    "trillion",
+    "quadrillion",
+    "quintillion",
+];
+
+fn digit_words(digits: &str) -> String {
+    digits
+        .chars()
+        .filter_map(|c| c.to_digit(10))
+        .map(|d| ONES[d as usize])
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// English words for `n`, e.g. 1999 → "one thousand nine hundred
+/// ninety-nine", with "and" before the tens in en-GB.
+fn cardinal(n: u64, locale: Locale) -> String {
+    if n == 0 {
+        return ONES[0].to_string();
+    }
+    let mut groups = Vec::new();
+    let mut rest = n;
+    while rest > 0 {
+        groups.push(rest % 1000);
+        rest /= 1000;
+    }
+    let mut words = Vec::new();
+    for (scale, &group) in groups.iter().enumerate().rev() {
+        if group == 0 {
+            continue;
+        }
+        // "one thousand and five"
+        if locale == Locale::EnGb && scale == 0 && group < 100 && n >= 1000 {
+            words.push("and".to_string());
+        }
+        words.push(below_thousand(group, locale));
+        if scale > 0 {
+            words.push(SCALES[scale].to_string());
+        }
+    }
+    words.join(" ")
+}
+
+fn below_thousand(n: u64, locale: Locale) -> String {
+    let below_hundred = |n: u64| match (n as usize / 10, n as usize % 10) {
+        (0 | 1, _) => ONES[n as usize].to_string(),
+        (tens, 0) => TENS[tens].to_string(),
+        (tens, ones) => format!("{}-{}", TENS[tens], ONES[ones]),
+    };
+    let and = if locale == Locale::EnGb { " and" } else { "" };
+    match (n / 100, n % 100) {
+        (0, rest) => below_hundred(rest),
+        (hundreds, 0) => format!("{} hundred", ONES[hundreds as usize]),
+        (hundreds, rest) => format!(
+            "{} hundred{and} {}",
+            ONES[hundreds as usize],
+            below_hundred(rest)
+        ),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn expand(classes: &[Expansion], text: &str) -> String {
+        Options {
+            expand: classes.to_vec(),
+            ..Options::default()
+        }
+        .apply(text)
+    }
+
+    fn numbers(locale: Locale, text: &str) -> String {
+        Options {
+            numbers: Some(locale),
+            ..Options::default()
+        }
+        .apply(text)
+    }
+
+    fn check(cases: &[(&str, &str)], rewrite: impl Fn(&str) -> String) {
+        for (input, expected) in cases {
+            assert_eq!(rewrite(input), *expected, "{input}");
+        }
+    }
+
+    #[test]
+    fn titles() {
+        check(
+            &[
+                ("Dr. Lee and Mr. Smith", "Doctor Lee and Mister Smith"),
+                ("(Prof. Xu)", "(Professor Xu)"),
+                ("Ask Mrs. Gray, Jr.", "Ask Missus Gray, Junior."),
+                ("Dr.Who", "Dr.Who"),
+            ],
+            |text| expand(&[Expansion::Titles], text),
+        );
+    }
+
+    #[test]
+    fn addresses() {
+        check(
+            &[
+                ("St. Louis", "Saint Louis"),
+                ("Main St.", "Main Street."),
+                ("12 St. is closed", "12 Street is closed"),
+                ("Visit Main St. Louis", "Visit Main Street Louis"),
+                ("Elm Dr. is closed", "Elm Drive is closed"),
+                ("Dr. Smith", "Dr. Smith"),
+                ("Apt. 4, Mt. Hood Ave.", "Apartment 4, Mount Hood Avenue."),
+            ],
+            |text| expand(&[Expansion::Addresses], text),
+        );
+    }
+
+    #[test]
+    fn common() {
+        check(
+            &[
+                ("fruit, e.g. apples", "fruit, for example apples"),
+                ("E.g. this", "For example this"),
+                ("apples, pears, etc. Then", "apples, pears, et cetera. Then"),
+                ("apples, etc. and pears", "apples, et cetera and pears"),
+                ("apples, etc.", "apples, et cetera."),
+                ("cats vs dogs", "cats versus dogs"),
+                ("\"i.e.\" means", "\"that is\" means"),
+            ],
+            |text| expand(&[Expansion::Common], text),
+        );
+    }
+
+    #[test]
+    fn units() {
+        check(
+            &[
+                ("5 km away", "5 kilometers away"),
+                ("1 km away", "1 kilometer away"),
+                ("5km and 50%", "5 kilometers and 50 percent"),
+                ("2.5 kg.", "2.5 kilograms."),
+                ("at 30 °C", "at 30 degrees Celsius"),
+                ("m is a letter", "m is a letter"),
+                ("5 apples", "5 apples"),
+            ],
+            |text| expand(&[Expansion::Units], text),
+        );
+    }
+
+    #[test]
+    fn all_classes_then_numbers() {
+        let options = Options {
+            expand: vec![Expansion::All],
+            ..Options::default()
+        };
+        let cfg = Config::default();
+        assert_eq!(
+            options
+                .with_defaults(&cfg)
+                .apply("Dr. Lee moved 5 km to St. Paul, e.g. for work."),
+            "Doctor Lee moved five kilometers to Saint Paul, for example for work."
+        );
+    }
+
+    #[test]
+    fn number_defaults() {
+        let mut cfg = Config::default();
+        let plain = Options::default();
+        assert_eq!(
+            plain.with_defaults(&cfg).apply("Es kostet 5 Euro"),
+            "Es kostet 5 Euro"
+        );
+        cfg.normalize_numbers = true;
+        assert_eq!(plain.with_defaults(&cfg).apply("5 Euro"), "five Euro");
+        let keep = Options {
+            keep_numbers: true,
+            ..Options::default()
+        };
+        assert_eq!(keep.with_defaults(&cfg).apply("5 Euro"), "5 Euro");
+    }
+
+    #[test]
+    fn currencies() {
+        check(
+            &[
+                ("$5", "five dollars"),
+                ("$1", "one dollar"),
+                ("$3.50", "three dollars and fifty cents"),
+                ("$0.99", "ninety-nine cents"),
+                ("$1.01", "one dollar and one cent"),
+                ("$2.00", "two dollars"),
+                ("€20", "twenty euros"),
+                ("£0.01", "one penny"),
+                ("£5.5", "five pounds and fifty pence"),
+                ("¥500", "five hundred yen"),
+                ("¥5.25", "five point two five yen"),
+                ("$1.005", "one point zero zero five dollars"),
+                ("$2.5M", "two point five million dollars"),
+                ("£3bn", "three billion pounds"),
+                ("$5x", "$5x"),
+                ("-$5", "minus five dollars"),
+                ("$1,250", "one thousand two hundred fifty dollars"),
+            ],
+            |text| numbers(Locale::EnUs, text),
+        );
+    }
+
+    #[test]
+    fn dates() {
+        check(
+            &[
+                ("3/4/2024", "March fourth, twenty twenty-four"),
+                ("2024-03-04", "March fourth, twenty twenty-four"),
+                ("13/4/2024", "13/4/2024"),
+                ("2024-3-4", "2024-3-4"),
+                ("on March 4.", "on March fourth."),
+                ("4 March", "the fourth of March"),
+                ("March 32", "March thirty-two"),
+            ],
+            |text| numbers(Locale::EnUs, text),
+        );
+        check(
+            &[
+                ("3/4/2024", "the third of April twenty twenty-four"),
+                ("13/4/2024", "the thirteenth of April twenty twenty-four"),
+                ("2024-03-04", "the fourth of March twenty twenty-four"),
+            ],
+            |text| numbers(Locale::EnGb, text),
+        );
+    }
+
+    #[test]
+    fn fractions() {
+        check(
+            &[
+                ("1/2", "one half"),
+                ("3/4", "three quarters"),
+                ("1/4", "one quarter"),
+                ("5/8", "five eighths"),
+                ("1/100", "one one hundredth"),
+                ("12/25", "12/25"),
+                ("24/7", "24/7"),
+                ("3/2", "3/2"),
+                ("0/4", "0/4"),
+            ],
+            |text| numbers(Locale::EnUs, text),
+        );
+    }
+
+    #[test]
+    fn ordinals_decades_percents() {
+        check(
+            &[
+                ("1st", "first"),
+                ("22nd", "twenty-second"),
+                ("103rd", "one hundred third"),
+                ("12th", "twelfth"),
+                ("20th", "twentieth"),
+                ("1x", "1x"),
+                ("01st", "01st"),
+                ("1990s", "nineteen nineties"),
+                ("2000s", "two thousands"),
+                ("1995s", "1995s"),
+                ("50%", "fifty percent"),
+                ("2.5%", "two point five percent"),
+                ("-5", "minus five"),
+                ("−3.5", "minus three point five"),
+                ("-x", "-x"),
+            ],
+            |text| numbers(Locale::EnUs, text),
+        );
+        assert_eq!(numbers(Locale::EnGb, "103rd"), "one hundred and third");
+    }
+
+    #[test]
+    fn years() {
+        check(
+            &[
+                ("in 1999", "in nineteen ninety-nine"),
+                ("In 1905,", "In nineteen oh five,"),
+                ("since 2005", "since two thousand five"),
+                ("until 2024.", "until twenty twenty-four."),
+                ("March 4, 2024", "March fourth, twenty twenty-four"),
+                ("4th 1800", "fourth eighteen hundred"),
+                ("1492 AD", "fourteen ninety-two AD"),
+                ("1500 apples", "one thousand five hundred apples"),
+                ("in 2100", "in two thousand one hundred"),
+                ("in 1,500", "in one thousand five hundred"),
+            ],
+            |text| numbers(Locale::EnUs, text),
+        );
+        assert_eq!(numbers(Locale::EnGb, "in 2005"), "in two thousand and five");
+    }
+
+    #[test]
+    fn plain_numbers() {
+        check(
+            &[
+                ("0", "zero"),
+                ("42", "forty-two"),
+                ("1,000", "one thousand"),
+                ("1,250.5", "one thousand two hundred fifty point five"),
+                ("3.14", "three point one four"),
+                ("007", "zero zero seven"),
+                ("1,00", "1,00"),
+                ("12,3456", "12,3456"),
+                ("5.", "five."),
+                ("1000000", "one million"),
+                (
+                    "18446744073709551615",
+                    "eighteen quintillion four hundred forty-six quadrillion seven hundred \
+                     forty-four trillion seventy-three billion seven hundred nine million five \
+                     hundred fifty-one thousand six hundred fifteen",
+                ),
+                ("18446744073709551616", "18446744073709551616"),
+                ("v1.2.3", "v1.2.3"),
+            ],
+            |text| numbers(Locale::EnUs, text),
+        );
+        check(
+            &[
+                ("105", "one hundred and five"),
+                ("1005", "one thousand and five"),
+                ("2,100", "two thousand one hundred"),
+            ],
+            |text| numbers(Locale::EnGb, text),
+        );
+    }
+
+    #[test]
+    fn whitespace_and_punctuation_are_kept() {
+        assert_eq!(
+            numbers(Locale::EnUs, "  (5),\n\"7\"!  "),
+            "  (five),\n\"seven\"!  "
+        );
+    }
+
+    #[test]
+    fn parse_names() {
+        assert_eq!("en_gb".parse::<Locale>().unwrap(), Locale::EnGb);
+        assert_eq!("EN-us".parse::<Locale>().unwrap(), Locale::EnUs);
+        assert!("fr-FR".parse::<Locale>().is_err());
+        assert_eq!(" Units ".parse::<Expansion>().unwrap(), Expansion::Units);
+        assert!("numerals".parse::<Expansion>().is_err());
+    }
+}
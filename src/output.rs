@@ -1,17 +1,423 @@
-use colored::Colorize;
+use std::fs::{self, File, OpenOptions};
+use std::io::Write;
+use std::path::Path;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Mutex, OnceLock};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use anyhow::{Context, Result};
+use colored::{ColoredString, Colorize};
+use indicatif::{ProgressBar, ProgressStyle};
+
+use crate::config;
+
+static LOG_FILE: OnceLock<Mutex<File>> = OnceLock::new();
+static VERBOSE: AtomicBool = AtomicBool::new(false);
+static THEME: OnceLock<Theme> = OnceLock::new();
+
+/// Palette for message labels, for terminals or eyes that don't suit the
+/// default colors.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Theme {
+    #[default]
+    Default,
+    /// Bright colors, and verbose details at full brightness
+    HighContrast,
+    /// Bold text only
+    Mono,
+}
+
+/// Theme names accepted by the `theme` key and `QWEN_TTS_THEME`.
+pub const THEMES: &[&str] = &["default", "high-contrast", "mono"];
+
+impl std::str::FromStr for Theme {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.to_lowercase().as_str() {
+            "default" => Ok(Self::Default),
+            "high-contrast" => Ok(Self::HighContrast),
+            "mono" => Ok(Self::Mono),
+            _ => Err(crate::error::ErrorKind::Config.error(format!(
+                "unknown theme: {s} (expected one of: {})",
+                THEMES.join(", ")
+            ))),
+        }
+    }
+}
+
+/// What a piece of output is for, so the theme can pick its style.
+#[derive(Clone, Copy)]
+enum Role {
+    Status,
+    Success,
+    Warn,
+    Error,
+    Verbose,
+}
+
+/// Set the palette for this process (from the `theme` key or
+/// `QWEN_TTS_THEME`). Only the first call takes effect.
+pub fn set_theme(theme: Theme) {
+    let _ = THEME.set(theme);
+}
+
+fn paint(role: Role, text: &str) -> ColoredString {
+    let theme = THEME.get().copied().unwrap_or_default();
+    match (theme, role) {
+        (Theme::Default, Role::Status) => text.cyan().bold(),
+        (Theme::Default, Role::Success) => text.green().bold(),
+        (Theme::Default, Role::Warn) => text.yellow().bold(),
+        (Theme::Default, Role::Error) => text.red().bold(),
+        (Theme::Default, Role::Verbose) => text.dimmed(),
+        (Theme::HighContrast, Role::Status) => text.bright_cyan().bold(),
+        (Theme::HighContrast, Role::Success) => text.bright_green().bold(),
+        (Theme::HighContrast, Role::Warn) => text.bright_yellow().bold().underline(),
+        (Theme::HighContrast, Role::Error) => text.bright_white().on_red().bold(),
+        (Theme::Mono, Role::Verbose) | (Theme::HighContrast, Role::Verbose) => text.normal(),
+        (Theme::Mono, _) => text.bold(),
+    }
+}
+
+/// Enable `verbose` messages (the global `--verbose` flag).
+pub fn set_verbose(enabled: bool) {
+    VERBOSE.store(enabled, Ordering::Relaxed);
+}
+
+pub fn is_verbose() -> bool {
+    VERBOSE.load(Ordering::Relaxed)
+}
+
+/// Mirror every message emitted through this module to `path` (appended,
+/// timestamped, without color) in addition to stderr.
+pub fn init_log(path: &Path) -> Result<()> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)
+            .with_context(|| format!("failed to create {}", parent.display()))?;
+    }
+    let file = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)
+        .with_context(|| format!("failed to open log file {}", path.display()))?;
+    let _ = LOG_FILE.set(Mutex::new(file));
+    Ok(())
+}
+
+fn now_secs() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
+
+/// Year, month, and day of `days` since the Unix epoch.
+fn civil_date(days: u64) -> (i64, i64, i64) {
+    // Civil-from-days conversion (Howard Hinnant's algorithm)
+    let z = days as i64 + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z.rem_euclid(146_097);
+    let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + i64::from(month <= 2);
+    (year, month, day)
+}
+
+/// Today's UTC date, e.g. `2024-01-25`.
+pub fn today() -> String {
+    let (year, month, day) = civil_date(now_secs() / 86_400);
+    format!("{year:04}-{month:02}-{day:02}")
+}
+
+/// UTC timestamp in RFC 3339 format, e.g. `2024-01-25T14:03:07Z`.
+pub(crate) fn timestamp() -> String {
+    let secs = now_secs();
+    let (year, month, day) = civil_date(secs / 86_400);
+    let rem = secs % 86_400;
+    format!(
+        "{year:04}-{month:02}-{day:02}T{:02}:{:02}:{:02}Z",
+        rem / 3600,
+        rem % 3600 / 60,
+        rem % 60
+    )
+}
+
+fn log(label: &str, message: &str) {
+    if let Some(file) = LOG_FILE.get() {
+        if let Ok(mut file) = file.lock() {
+            let _ = writeln!(file, "{} {label} {message}", timestamp());
+        }
+    }
+}
 
 pub fn status(label: &str, message: &str) {
-    eprintln!("{} {}", label.cyan().bold(), message);
+    eprintln!("{} {}", paint(Role::Status, label), message);
+    log(label, message);
+}
+
+/// Diagnostic detail shown only with `--verbose` (always written to the log file).
+pub fn verbose(label: &str, message: &str) {
+    if is_verbose() {
+        eprintln!(
+            "{} {}",
+            paint(Role::Verbose, label),
+            paint(Role::Verbose, message)
+        );
+    }
+    log(label, message);
 }
 
 pub fn success(message: &str) {
-    eprintln!("{} {}", "Done!".green().bold(), message);
+    eprintln!("{} {}", paint(Role::Success, "Done!"), message);
+    log("Done!", message);
 }
 
 pub fn warn(message: &str) {
-    eprintln!("{} {}", "Warning:".yellow().bold(), message);
+    eprintln!("{} {}", paint(Role::Warn, "Warning:"), message);
+    log("Warning:", message);
 }
 
 pub fn error(message: &str) {
-    eprintln!("{} {}", "Error:".red().bold(), message);
+    eprintln!("{} {}", paint(Role::Error, "Error:"), message);
+    log("Error:", message);
+}
+
+static BACKEND_STDERR: Mutex<Option<String>> = Mutex::new(None);
+
+/// Remember the stderr of a failed backend run for `save_last_error`.
+pub fn record_backend_stderr(stderr: &str) {
+    if let Ok(mut slot) = BACKEND_STDERR.lock() {
+        *slot = Some(stderr.to_string());
+    }
+}
+
+/// Write a failed command's error, plus any captured backend stderr, to
+/// `path`, replacing the previous report.
+pub fn save_last_error(path: &Path, message: &str) -> Result<()> {
+    let command = redacted_args(std::env::args().collect());
+    let mut report = format!(
+        "time: {}\ncommand: {}\n\nerror: {message}\n",
+        timestamp(),
+        command.join(" ")
+    );
+    if let Some(stderr) = BACKEND_STDERR.lock().ok().and_then(|s| s.clone()) {
+        report.push_str("\nbackend stderr:\n");
+        report.push_str(stderr.trim_end());
+        report.push('\n');
+    }
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)
+            .with_context(|| format!("failed to create {}", parent.display()))?;
+    }
+    fs::write(path, report).with_context(|| format!("failed to write {}", path.display()))
+}
+
+/// Global flags that take their value as the next argument (see `Cli` in
+/// main.rs), so the value isn't mistaken for a positional argument.
+const GLOBAL_VALUE_FLAGS: &[&str] = &[
+    "--config",
+    "--backend",
+    "--timeout",
+    "--jobs",
+    "--models-dir",
+    "--output-dir",
+    "--log-file",
+];
+
+/// The command line with the value of `config set hf_token|proxy`
+/// redacted, so secrets don't end up in last_error.log.
+fn redacted_args(mut args: Vec<String>) -> Vec<String> {
+    // Positional arguments after the program name; global flags may sit
+    // anywhere between them
+    let mut positional = Vec::new();
+    let mut i = 1;
+    while i < args.len() {
+        let arg = args[i].as_str();
+        if arg == "--" {
+            positional.extend(i + 1..args.len());
+            break;
+        }
+        if GLOBAL_VALUE_FLAGS.contains(&arg) {
+            i += 2;
+            continue;
+        }
+        if !arg.starts_with('-') {
+            positional.push(i);
+        }
+        i += 1;
+    }
+    let [command, action, key, value, ..] = positional[..] else {
+        return args;
+    };
+    if args[command] != "config" || args[action] != "set" {
+        return args;
+    }
+    let redact = match args[key].as_str() {
+        "hf_token" => config::redact_token,
+        "proxy" => config::redact_proxy,
+        _ => return args,
+    };
+    args[value] = redact(&args[value]);
+    args
+}
+
+/// Print the report written by `save_last_error`.
+pub fn print_last_error(path: &Path) -> Result<()> {
+    print_log(path, "No errors recorded yet")
+}
+
+/// Print the log at `path`, or `missing` as a status when there is none.
+pub fn print_log(path: &Path, missing: &str) -> Result<()> {
+    match fs::read_to_string(path) {
+        Ok(report) => {
+            print!("{report}");
+            Ok(())
+        }
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+            status("Logs", missing);
+            Ok(())
+        }
+        Err(e) => Err(e).with_context(|| format!("failed to read {}", path.display())),
+    }
+}
+
+/// Ask a yes/no question on stderr; an empty answer or unreadable stdin
+/// picks the default.
+pub fn prompt_yn(question: &str, default_yes: bool) -> bool {
+    let hint = if default_yes { "[Y/n]" } else { "[y/N]" };
+    eprint!("{question} {hint} ");
+    std::io::stderr().flush().ok();
+    let mut input = String::new();
+    if std::io::stdin().read_line(&mut input).is_err() {
+        return default_yes;
+    }
+    let input = input.trim().to_lowercase();
+    if input.is_empty() {
+        return default_yes;
+    }
+    input.starts_with('y')
+}
+
+/// Print `value` to stdout as pretty JSON, for `--json` output modes.
+pub fn print_json<T: serde::Serialize + ?Sized>(value: &T) -> Result<()> {
+    let text = serde_json::to_string_pretty(value).context("failed to serialize JSON")?;
+    println!("{text}");
+    Ok(())
+}
+
+/// Overall progress bar for multi-item jobs, counted in words. Wrap backend
+/// calls in `ProgressBar::suspend` so their output doesn't garble the bar.
+pub fn word_progress(total_words: u64) -> ProgressBar {
+    let pb = ProgressBar::new(total_words);
+    pb.set_style(
+        ProgressStyle::with_template(
+            "{prefix:.cyan.bold} [{bar:30}] {pos}/{len} words ({msg}) {elapsed_precise} elapsed, ETA {eta_precise}",
+        )
+        .expect("valid progress template")
+        .progress_chars("=> "),
+    );
+    pb.set_prefix("Progress");
+    pb
+}
+
+/// Collects per-item outcomes of a bulk operation so failures are reported
+/// together at the end instead of aborting on the first one.
+pub struct BatchReport {
+    what: String,
+    succeeded: usize,
+    failures: Vec<(String, String)>,
+}
+
+impl BatchReport {
+    /// `what` names the items in the summary, e.g. "models".
+    pub fn new(what: &str) -> Self {
+        Self {
+            what: what.to_string(),
+            succeeded: 0,
+            failures: Vec::new(),
+        }
+    }
+
+    /// Record the outcome for `item`, warning immediately on failure.
+    pub fn record<T>(&mut self, item: &str, result: Result<T>) -> Option<T> {
+        match result {
+            Ok(value) => {
+                self.succeeded += 1;
+                Some(value)
+            }
+            Err(e) => {
+                warn(&format!("{item}: {e:#}"));
+                self.failures.push((item.to_string(), format!("{e:#}")));
+                None
+            }
+        }
+    }
+
+    /// Print the summary. Returns an error if any item failed.
+    pub fn finish(self) -> Result<()> {
+        let total = self.succeeded + self.failures.len();
+        if self.failures.is_empty() {
+            success(&format!("{total} {} processed.", self.what));
+            return Ok(());
+        }
+
+        eprintln!();
+        eprintln!(
+            "{} {} of {total} {} failed:",
+            paint(Role::Error, "Summary:"),
+            self.failures.len(),
+            self.what
+        );
+        for (item, reason) in &self.failures {
+            eprintln!("  {} {}", item.bold(), reason);
+            log("Failed:", &format!("{item}: {reason}"));
+        }
+        anyhow::bail!("{} of {total} {} failed", self.failures.len(), self.what)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn secrets_are_redacted_from_the_command() {
+        let redact =
+            |line: &str| redacted_args(line.split(' ').map(String::from).collect()).join(" ");
+        let cases = [
+            (
+                "q config set hf_token hf_abcdefghijkl",
+                "q config set hf_token ****ijkl",
+            ),
+            (
+                "q config --verbose set proxy http://u:p@host:8080",
+                "q config --verbose set proxy http://host:8080",
+            ),
+            (
+                "q config set proxy --quiet u:p@host",
+                "q config set proxy --quiet host",
+            ),
+            ("q config set voice Ryan", "q config set voice Ryan"),
+            ("q speak set proxy a:b@c", "q speak set proxy a:b@c"),
+            ("q config set hf_token", "q config set hf_token"),
+            (
+                "q config set hf_token --log-file x.log hf_abcdefghijkl",
+                "q config set hf_token --log-file x.log ****ijkl",
+            ),
+            (
+                "q config set --backend cuda hf_token hf_abcdefghijkl",
+                "q config set --backend cuda hf_token ****ijkl",
+            ),
+            (
+                "q --config set config set proxy u:p@host",
+                "q --config set config set proxy host",
+            ),
+        ];
+        for (line, expected) in cases {
+            assert_eq!(redact(line), expected, "{line}");
+        }
+    }
 }
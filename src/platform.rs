@@ -1,15 +1,22 @@
 use std::fmt;
+use std::path::{Path, PathBuf};
 use std::process::Command;
 
 use anyhow::Result;
+use colored::Colorize;
 use serde::{Deserialize, Serialize};
 
+use crate::config;
+use crate::error::ErrorKind;
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "lowercase")]
 pub enum Backend {
     Mlx,
     Cuda,
     Cpu,
+    /// User-defined command line from the `custom_command` config key
+    Custom,
 }
 
 impl fmt::Display for Backend {
@@ -18,6 +25,7 @@ impl fmt::Display for Backend {
             Backend::Mlx => write!(f, "mlx"),
             Backend::Cuda => write!(f, "cuda"),
             Backend::Cpu => write!(f, "cpu"),
+            Backend::Custom => write!(f, "custom"),
         }
     }
 }
@@ -29,7 +37,10 @@ impl std::str::FromStr for Backend {
             "mlx" => Ok(Backend::Mlx),
             "cuda" => Ok(Backend::Cuda),
             "cpu" => Ok(Backend::Cpu),
-            _ => anyhow::bail!("unknown backend: {s} (expected mlx, cuda, or cpu)"),
+            "custom" => Ok(Backend::Custom),
+            _ => Err(ErrorKind::Config.error(format!(
+                "unknown backend: {s} (expected mlx, cuda, cpu, or custom)"
+            ))),
         }
     }
 }
@@ -61,8 +72,27 @@ pub fn detect_os() -> Os {
     }
 }
 
+/// Whether the machine is an Apple Silicon Mac. Checks the hardware rather
+/// than the build target, so an x86_64 build running under Rosetta still
+/// reports true.
 pub fn is_apple_silicon() -> bool {
-    cfg!(target_os = "macos") && cfg!(target_arch = "aarch64")
+    if !cfg!(target_os = "macos") {
+        return false;
+    }
+    cfg!(target_arch = "aarch64") || sysctl_flag("hw.optional.arm64")
+}
+
+/// Whether this process is an x86_64 binary translated by Rosetta 2.
+pub fn is_rosetta() -> bool {
+    cfg!(target_os = "macos") && sysctl_flag("sysctl.proc_translated")
+}
+
+/// Read a macOS sysctl that reports `1` when set. Missing keys read as false.
+fn sysctl_flag(name: &str) -> bool {
+    Command::new("sysctl")
+        .args(["-n", name])
+        .output()
+        .is_ok_and(|out| out.status.success() && String::from_utf8_lossy(&out.stdout).trim() == "1")
 }
 
 pub fn has_nvidia_gpu() -> bool {
@@ -74,13 +104,55 @@ pub fn has_nvidia_gpu() -> bool {
         .unwrap_or(false)
 }
 
+/// Free memory on the emptiest NVIDIA GPU in MiB, from `nvidia-smi`.
+/// `None` when there is no NVIDIA GPU or the query fails.
+pub fn gpu_free_memory_mib() -> Option<u64> {
+    let out = Command::new("nvidia-smi")
+        .args(["--query-gpu=memory.free", "--format=csv,noheader,nounits"])
+        .stderr(std::process::Stdio::null())
+        .output()
+        .ok()
+        .filter(|out| out.status.success())?;
+    String::from_utf8_lossy(&out.stdout)
+        .lines()
+        .filter_map(|l| l.trim().parse::<u64>().ok())
+        .max()
+}
+
 pub fn detect_backend() -> Backend {
-    let os = detect_os();
-    match os {
-        Os::Mac if is_apple_silicon() => Backend::Mlx,
-        _ if has_nvidia_gpu() => Backend::Cuda,
-        _ => Backend::Cpu,
+    accelerated_backends()
+        .first()
+        .copied()
+        .unwrap_or(Backend::Cpu)
+}
+
+/// GPU backends this machine could run, in the order auto-detection
+/// prefers them.
+fn accelerated_backends() -> Vec<Backend> {
+    let mut found = Vec::new();
+    if detect_os() == Os::Mac && is_apple_silicon() {
+        found.push(Backend::Mlx);
     }
+    if has_nvidia_gpu() {
+        found.push(Backend::Cuda);
+    }
+    found
+}
+
+/// When more than one GPU backend would work, which one auto-detection
+/// picked and how to pick another. `None` when the choice was clear.
+pub fn detection_note() -> Option<String> {
+    let found = accelerated_backends();
+    let (picked, others) = found
+        .split_first()
+        .filter(|(_, others)| !others.is_empty())?;
+    let others: Vec<String> = others.iter().map(Backend::to_string).collect();
+    Some(format!(
+        "{} would also work here; using {picked}, which auto-detection prefers. \
+         Switch with `qwen-tts config set backend {}`",
+        others.join(" and "),
+        others[0]
+    ))
 }
 
 pub fn platform_summary() -> String {
@@ -93,3 +165,227 @@ pub fn platform_summary() -> String {
     };
     format!("{os} ({arch}) — backend: {backend}")
 }
+
+/// Reveal a path in the platform file manager.
+pub fn open_in_file_manager(path: &Path) -> Result<()> {
+    let opener = match detect_os() {
+        Os::Mac => "open",
+        Os::Windows => "explorer",
+        Os::Linux => "xdg-open",
+    };
+    let status = Command::new(opener)
+        .arg(path)
+        .status()
+        .map_err(|e| anyhow::anyhow!("failed to run {opener}: {e}"))?;
+    // explorer.exe returns a non-zero exit code even on success
+    if !status.success() && detect_os() != Os::Windows {
+        anyhow::bail!("{opener} exited with {status}");
+    }
+    Ok(())
+}
+
+/// Split a command line into arguments the way a POSIX shell would for
+/// plain words: whitespace separates arguments, single quotes are literal,
+/// and double quotes allow `\"` and `\\` escapes. No expansion is done.
+pub fn split_command(line: &str) -> Result<Vec<String>> {
+    let mut args = Vec::new();
+    let mut current = String::new();
+    let mut in_word = false;
+    let mut chars = line.chars();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '\'' => {
+                in_word = true;
+                loop {
+                    match chars.next() {
+                        Some('\'') => break,
+                        Some(c) => current.push(c),
+                        None => anyhow::bail!("unterminated ' in command: {line}"),
+                    }
+                }
+            }
+            '"' => {
+                in_word = true;
+                loop {
+                    match chars.next() {
+                        Some('"') => break,
+                        Some('\\') => match chars.next() {
+                            Some(c @ ('"' | '\\')) => current.push(c),
+                            Some(c) => {
+                                current.push('\\');
+                                current.push(c);
+                            }
+                            None => anyhow::bail!("unterminated \" in command: {line}"),
+                        },
+                        Some(c) => current.push(c),
+                        None => anyhow::bail!("unterminated \" in command: {line}"),
+                    }
+                }
+            }
+            '\\' => {
+                in_word = true;
+                if let Some(c) = chars.next() {
+                    current.push(c);
+                }
+            }
+            c if c.is_whitespace() => {
+                if in_word {
+                    args.push(std::mem::take(&mut current));
+                    in_word = false;
+                }
+            }
+            c => {
+                in_word = true;
+                current.push(c);
+            }
+        }
+    }
+    if in_word {
+        args.push(current);
+    }
+    Ok(args)
+}
+
+/// Locate an executable on PATH.
+pub fn find_in_path(name: &str) -> Option<PathBuf> {
+    let path = std::env::var_os("PATH")?;
+    std::env::split_paths(&path).find_map(|dir| {
+        let candidate = dir.join(name);
+        if candidate.is_file() {
+            return Some(candidate);
+        }
+        if cfg!(target_os = "windows") {
+            let exe = dir.join(format!("{name}.exe"));
+            if exe.is_file() {
+                return Some(exe);
+            }
+        }
+        None
+    })
+}
+
+fn has_git_lfs() -> bool {
+    Command::new("git")
+        .args(["lfs", "version"])
+        .stdout(std::process::Stdio::null())
+        .stderr(std::process::Stdio::null())
+        .status()
+        .map(|s| s.success())
+        .unwrap_or(false)
+}
+
+/// Audio players tried by playback on this OS, in order.
+pub fn players() -> &'static [&'static str] {
+    match detect_os() {
+        Os::Mac => &["afplay"],
+        Os::Windows => &["powershell"],
+        Os::Linux => &["aplay", "paplay", "ffplay"],
+    }
+}
+
+fn print_check(label: &str, found: bool, detail: &str) {
+    let mark = if found { "ok".green() } else { "missing".red() };
+    println!("  {label:<14}{mark:<8} {detail}");
+}
+
+/// Print platform detection results and the availability of external tools.
+pub fn show() -> Result<()> {
+    let cfg = config::read_existing();
+    let detected = detect_backend();
+
+    println!("{}", "Platform".bold());
+    println!("  {:<14}{}", "OS", detect_os());
+    println!("  {:<14}{}", "Arch", std::env::consts::ARCH);
+    println!(
+        "  {:<14}{}",
+        "Apple Silicon",
+        match (is_apple_silicon(), is_rosetta()) {
+            (true, true) => "yes (this build runs under Rosetta)",
+            (true, false) => "yes",
+            (false, _) => "no",
+        }
+    );
+    println!(
+        "  {:<14}{}",
+        "NVIDIA GPU",
+        match gpu_free_memory_mib() {
+            Some(free) => format!("yes ({free} MiB free)"),
+            None if has_nvidia_gpu() => "yes".to_string(),
+            None => "no".to_string(),
+        }
+    );
+    println!("  {:<14}{detected}", "Detected");
+    if let Some(note) = detection_note() {
+        println!("  {:<14}{}", "", note.dimmed());
+    }
+    match &cfg {
+        Some(cfg) => println!("  {:<14}{}", "Configured", cfg.backend),
+        None => println!("  {:<14}(no config yet)", "Configured"),
+    }
+
+    println!();
+    println!("{}", "Tools".bold());
+    let python = cfg
+        .as_ref()
+        .map(|c| config::expand_path(&c.python_path))
+        .unwrap_or_else(|| config::expand_path(&config::Config::default().python_path));
+    print_check("python", python.exists(), &python.display().to_string());
+
+    let git = find_in_path("git");
+    print_check(
+        "git",
+        git.is_some(),
+        &git.map(|p| p.display().to_string()).unwrap_or_default(),
+    );
+    print_check("git-lfs", has_git_lfs(), "");
+
+    let ffmpeg = find_in_path("ffmpeg");
+    print_check(
+        "ffmpeg",
+        ffmpeg.is_some(),
+        &ffmpeg.map(|p| p.display().to_string()).unwrap_or_default(),
+    );
+
+    let candidates: Vec<String> = match cfg.as_ref().and_then(|c| c.player.clone()) {
+        Some(player) => vec![player],
+        None => players().iter().map(|p| p.to_string()).collect(),
+    };
+    let player = candidates.iter().find_map(|p| find_in_path(p));
+    print_check(
+        "player",
+        player.is_some(),
+        &player
+            .map(|p| p.display().to_string())
+            .unwrap_or_else(|| format!("tried {}", candidates.join(", "))),
+    );
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn split_command_words_and_quotes() {
+        let cases: &[(&str, &[&str])] = &[
+            ("", &[]),
+            ("  tts  --text {text} ", &["tts", "--text", "{text}"]),
+            ("say 'a b' \"c d\"", &["say", "a b", "c d"]),
+            (r#"x "a \"q\" \\ \n""#, &["x", r#"a "q" \ \n"#]),
+            (r"a\ b 'it''s'", &["a b", "its"]),
+            ("'' \"\"", &["", ""]),
+        ];
+        for (line, expected) in cases {
+            assert_eq!(split_command(line).unwrap(), *expected, "{line}");
+        }
+    }
+
+    #[test]
+    fn split_command_unterminated_quotes() {
+        assert!(split_command("say 'open").is_err());
+        assert!(split_command("say \"open").is_err());
+        assert!(split_command("say \"open\\").is_err());
+    }
+}
@@ -0,0 +1,84 @@
+use std::path::Path;
+use std::sync::OnceLock;
+
+use crate::output;
+
+/// Progress of downloads, backend runs, and batch jobs, for callers that
+/// embed qwen-tts and want to show it their own way.
+#[derive(Debug, Clone, Copy)]
+pub enum Event<'a> {
+    /// A model download started; `source` is "huggingface" or "git"
+    DownloadStarted {
+        repo: &'a str,
+        source: &'a str,
+    },
+    /// Bytes on disk so far for the download in progress
+    DownloadBytes {
+        repo: &'a str,
+        bytes: u64,
+    },
+    /// A backend run started writing `output`
+    GenerationStarted {
+        output: &'a Path,
+    },
+    GenerationFinished {
+        output: &'a Path,
+        ok: bool,
+    },
+    /// Item `index` (1-based) of a `total`-item job started
+    ItemStarted {
+        index: usize,
+        total: usize,
+        name: &'a str,
+    },
+    ItemFinished {
+        index: usize,
+        total: usize,
+        ok: bool,
+    },
+}
+
+/// Receives progress events. Called from worker threads when several
+/// backend runs happen at once.
+pub trait Progress: Send + Sync {
+    fn on_event(&self, event: &Event);
+}
+
+/// The CLI's handler: prints status lines on stderr. Byte counts and
+/// backend runs are left to the tools' own output.
+pub struct Terminal;
+
+impl Progress for Terminal {
+    fn on_event(&self, event: &Event) {
+        match *event {
+            Event::DownloadStarted { repo, source } => {
+                let tool = if source == "git" {
+                    "git clone"
+                } else {
+                    "huggingface_hub"
+                };
+                output::status("Downloading", &format!("{repo} via {tool}..."));
+            }
+            Event::ItemStarted { index, total, name } => {
+                output::status("Item", &format!("{index}/{total}: {name}"));
+            }
+            _ => {}
+        }
+    }
+}
+
+static HANDLER: OnceLock<Box<dyn Progress>> = OnceLock::new();
+
+/// Install the process-wide progress handler. Only the first call takes
+/// effect; returns false if a handler was already set. Without one, events
+/// are rendered like the CLI does.
+pub fn set_handler(handler: impl Progress + 'static) -> bool {
+    HANDLER.set(Box::new(handler)).is_ok()
+}
+
+pub(crate) fn emit(event: Event) {
+    match HANDLER.get() {
+        Some(handler) => handler.on_event(&event),
+        None => Terminal.on_event(&event),
+    }
+}
@@ -0,0 +1,304 @@
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::PathBuf;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use anyhow::Result;
+use colored::Colorize;
+use serde::{Deserialize, Serialize};
+
+use crate::bench;
+use crate::config::{self, Config};
+use crate::error::ErrorKind;
+use crate::output;
+
+/// Weight kept by older runs each time a new one is recorded, so estimates
+/// follow recent hardware and model changes.
+const DECAY: f64 = 0.9;
+
+/// Rolling fit of backend run time against text length, for one backend
+/// and variant: `secs ≈ overhead + chars × secs_per_char`.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+struct Throughput {
+    runs: u64,
+    weight: f64,
+    sum_chars: f64,
+    sum_secs: f64,
+    sum_chars_sq: f64,
+    sum_chars_secs: f64,
+}
+
+impl Throughput {
+    fn add(&mut self, chars: f64, secs: f64) {
+        self.runs += 1;
+        self.weight = self.weight * DECAY + 1.0;
+        self.sum_chars = self.sum_chars * DECAY + chars;
+        self.sum_secs = self.sum_secs * DECAY + secs;
+        self.sum_chars_sq = self.sum_chars_sq * DECAY + chars * chars;
+        self.sum_chars_secs = self.sum_chars_secs * DECAY + chars * secs;
+    }
+
+    fn predict(&self, chars: f64) -> Option<f64> {
+        if self.weight <= 0.0 || self.sum_chars <= 0.0 {
+            return None;
+        }
+        let mean_chars = self.sum_chars / self.weight;
+        let mean_secs = self.sum_secs / self.weight;
+        let variance = self.sum_chars_sq / self.weight - mean_chars * mean_chars;
+        // Texts of similar length can't separate startup from per-character
+        // cost, so fall back to a plain ratio
+        if variance <= (mean_chars * 0.1).powi(2) {
+            return Some(mean_secs / mean_chars * chars);
+        }
+        let covariance = self.sum_chars_secs / self.weight - mean_chars * mean_secs;
+        let per_char = (covariance / variance).max(0.0);
+        let overhead = (mean_secs - per_char * mean_chars).max(0.0);
+        Some(overhead + per_char * chars)
+    }
+}
+
+/// Characters synthesized on one UTC day with one voice and model.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct Usage {
+    day: String,
+    voice: String,
+    model: String,
+    runs: u64,
+    chars: u64,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct Stats {
+    #[serde(default)]
+    throughput: BTreeMap<String, Throughput>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    usage: Vec<Usage>,
+}
+
+/// Serializes read-modify-write of the stats file between parallel jobs.
+static LOCK: Mutex<()> = Mutex::new(());
+
+/// ~/.qwen-tts/stats.toml
+pub fn stats_path() -> PathBuf {
+    config::base_dir().join("stats.toml")
+}
+
+fn key(cfg: &Config) -> String {
+    format!("{}/{}", cfg.backend, cfg.model_variant)
+}
+
+fn read() -> Stats {
+    fs::read_to_string(stats_path())
+        .ok()
+        .and_then(|text| toml::from_str(&text).ok())
+        .unwrap_or_default()
+}
+
+/// Add one finished backend run to the model for the configured variant
+/// and to today's character tally for `voice`. Failures to save are
+/// ignored: statistics are never worth an error.
+pub fn record(cfg: &Config, voice: &str, chars: usize, elapsed: Duration) {
+    let _guard = LOCK.lock();
+    let mut stats = read();
+    stats
+        .throughput
+        .entry(key(cfg))
+        .or_default()
+        .add(chars as f64, elapsed.as_secs_f64());
+
+    let (day, model) = (output::today(), key(cfg));
+    let existing = stats
+        .usage
+        .iter_mut()
+        .find(|u| u.day == day && u.voice == voice && u.model == model);
+    match existing {
+        Some(usage) => {
+            usage.runs += 1;
+            usage.chars += chars as u64;
+        }
+        None => stats.usage.push(Usage {
+            day,
+            voice: voice.to_string(),
+            model,
+            runs: 1,
+            chars: chars as u64,
+        }),
+    }
+    if let Ok(text) = toml::to_string_pretty(&stats) {
+        fs::write(stats_path(), text).ok();
+    }
+}
+
+/// Expected seconds for one backend run per entry of `runs` (character
+/// counts), spread over `jobs` parallel workers. Without recorded runs, a
+/// matching `qwen-tts bench` result is used.
+fn estimate(cfg: &Config, runs: &[usize], jobs: usize) -> Option<f64> {
+    let total: f64 = match read().throughput.get(&key(cfg)) {
+        Some(fit) => runs
+            .iter()
+            .map(|&chars| fit.predict(chars as f64))
+            .sum::<Option<f64>>()?,
+        None => {
+            let text = fs::read_to_string(bench::results_path()).ok()?;
+            let bench: bench::BenchResult = toml::from_str(&text).ok()?;
+            if bench.backend != cfg.backend.to_string()
+                || bench.model_variant != cfg.model_variant
+                || bench.chars_per_sec <= 0.0
+            {
+                return None;
+            }
+            runs.iter()
+                .map(|&chars| bench.load_secs + chars as f64 / bench.chars_per_sec)
+                .sum()
+        }
+    };
+    Some(total / jobs.clamp(1, runs.len().max(1)) as f64)
+}
+
+/// Reports a command's expected run time up front and the actual time,
+/// next to the estimate, when it finishes.
+pub struct Timer {
+    start: Instant,
+    estimate: Option<f64>,
+}
+
+impl Timer {
+    pub fn start(cfg: &Config, runs: &[usize]) -> Self {
+        let estimate = estimate(cfg, runs, cfg.max_jobs);
+        if let Some(secs) = estimate {
+            let chars: usize = runs.iter().sum();
+            output::status(
+                "Estimate",
+                &format!("about {} for {chars} characters", format_secs(secs)),
+            );
+        }
+        Self {
+            start: Instant::now(),
+            estimate,
+        }
+    }
+
+    pub fn finish(self) {
+        let actual = self.start.elapsed().as_secs_f64();
+        match self.estimate {
+            Some(estimate) => output::status(
+                "Finished",
+                &format!(
+                    "in {} (estimated {})",
+                    format_secs(actual),
+                    format_secs(estimate)
+                ),
+            ),
+            None => output::verbose("Finished", &format!("in {}", format_secs(actual))),
+        }
+    }
+}
+
+fn format_secs(secs: f64) -> String {
+    if secs < 60.0 {
+        format!("{secs:.1}s")
+    } else {
+        format!("{}m {:02}s", (secs / 60.0) as u64, secs as u64 % 60)
+    }
+}
+
+/// Runs and characters summed over a group of usage entries.
+#[derive(Debug, Default, Serialize)]
+struct Totals {
+    runs: u64,
+    chars: u64,
+}
+
+#[derive(Serialize)]
+struct Report {
+    total: Totals,
+    days: BTreeMap<String, Totals>,
+    voices: BTreeMap<String, Totals>,
+    models: BTreeMap<String, Totals>,
+}
+
+/// `qwen-tts stats`: characters synthesized per day, voice, and model,
+/// optionally only from `since` (a `YYYY-MM-DD` date) on.
+pub fn show(json: bool, since: Option<&str>) -> Result<()> {
+    if let Some(date) = since {
+        let valid = date.len() == 10
+            && date.char_indices().all(|(i, c)| match i {
+                4 | 7 => c == '-',
+                _ => c.is_ascii_digit(),
+            });
+        if !valid {
+            return Err(ErrorKind::Config.error(format!(
+                "--since {date} is not a date; use the YYYY-MM-DD form, e.g. 2024-01-25"
+            )));
+        }
+    }
+
+    let mut report = Report {
+        total: Totals::default(),
+        days: BTreeMap::new(),
+        voices: BTreeMap::new(),
+        models: BTreeMap::new(),
+    };
+    let usage = read().usage;
+    // ISO dates sort as strings
+    for entry in usage
+        .iter()
+        .filter(|u| since.is_none_or(|d| u.day.as_str() >= d))
+    {
+        for totals in [
+            &mut report.total,
+            report.days.entry(entry.day.clone()).or_default(),
+            report.voices.entry(entry.voice.clone()).or_default(),
+            report.models.entry(entry.model.clone()).or_default(),
+        ] {
+            totals.runs += entry.runs;
+            totals.chars += entry.chars;
+        }
+    }
+
+    if json {
+        return output::print_json(&report);
+    }
+    if report.total.runs == 0 {
+        match since {
+            Some(date) => println!("No speech synthesized since {date}."),
+            None => println!("No speech synthesized yet."),
+        }
+        return Ok(());
+    }
+
+    let groups = [
+        ("By day", &report.days),
+        ("By voice", &report.voices),
+        ("By model", &report.models),
+    ];
+    let width = groups
+        .iter()
+        .flat_map(|(_, group)| group.keys())
+        .map(|name| name.chars().count())
+        .max()
+        .unwrap_or(0);
+    for (title, group) in groups {
+        println!("{}", title.bold());
+        for (name, totals) in group {
+            println!("  {}", format_totals(&format!("{name:<width$}"), totals));
+        }
+        println!();
+    }
+    let total = format!("{:<width$}", "Total");
+    println!(
+        "  {}",
+        format_totals(&total.bold().to_string(), &report.total)
+    );
+    Ok(())
+}
+
+fn format_totals(label: &str, totals: &Totals) -> String {
+    format!(
+        "{label}  {:>10} chars in {} run{}",
+        totals.chars,
+        totals.runs,
+        if totals.runs == 1 { "" } else { "s" }
+    )
+}
@@ -1,11 +1,151 @@
 use std::fs;
+use std::io::IsTerminal;
+use std::path::Path;
+use std::process::Command;
 
 use anyhow::{Context, Result};
 use colored::Colorize;
+use serde::{Deserialize, Serialize};
 
-use crate::config;
+use crate::audio;
+use crate::config::{self, Config};
+use crate::editor;
+use crate::error::ErrorKind;
+use crate::generate;
+use crate::models;
+use crate::normalize;
+use crate::output;
+use crate::platform::Backend;
 
-pub fn list() -> Result<()> {
+/// Prints the transcript of the WAV at argv[1]. Tries mlx-whisper, then
+/// openai-whisper, then a transformers ASR pipeline.
+const TRANSCRIBE_SCRIPT: &str = r#"
+import sys
+path = sys.argv[1]
+try:
+    import mlx_whisper
+    text = mlx_whisper.transcribe(path)["text"]
+except ImportError:
+    try:
+        import whisper
+        text = whisper.load_model("base").transcribe(path)["text"]
+    except ImportError:
+        from transformers import pipeline
+        asr = pipeline("automatic-speech-recognition", model="openai/whisper-base")
+        text = asr(path)["text"]
+print()
+print(" ".join(text.split()))
+"#;
+
+/// Metadata stored as `<name>.toml` next to an enrolled voice.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct VoiceMeta {
+    /// Sample rate of the reference audio as provided
+    pub original_sample_rate: u32,
+    /// Sample rate of the stored `<name>.wav`
+    pub sample_rate: u32,
+    /// `audio::content_hash` of the reference audio as provided, to spot
+    /// the same clip enrolled twice
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub content_hash: Option<String>,
+    /// Model variant `clone --voice` uses for this voice instead of the
+    /// configured one
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub model_variant: Option<String>,
+}
+
+fn read_meta(voices_dir: &Path, name: &str) -> Option<VoiceMeta> {
+    let text = fs::read_to_string(voices_dir.join(format!("{name}.toml"))).ok()?;
+    toml::from_str(&text).ok()
+}
+
+/// The model variant recorded for the saved voice `name`, if any.
+pub(crate) fn preferred_variant(cfg: &Config, name: &str) -> Option<String> {
+    read_meta(&config::expand_path(&cfg.voices_dir), name)?.model_variant
+}
+
+/// The saved voice other than `name` whose audio has `hash`. Voices
+/// enrolled before hashes were recorded are hashed from their stored file.
+fn find_duplicate(cfg: &Config, name: &str, hash: &str) -> Option<String> {
+    let voices_dir = config::expand_path(&cfg.voices_dir);
+    saved_names(cfg)
+        .into_iter()
+        .filter(|other| other != name)
+        .find(
+            |other| match read_meta(&voices_dir, other).and_then(|m| m.content_hash) {
+                Some(stored) => stored == hash,
+                None => audio::content_hash(&voices_dir.join(format!("{other}.wav")))
+                    .is_ok_and(|stored| stored == hash),
+            },
+        )
+}
+
+/// Share the audio of the saved voice `other` as `dest` through a hard link,
+/// copying only where links aren't supported. Returns `other`'s metadata.
+fn link_reference(voices_dir: &Path, other: &str, dest: &Path) -> Result<Option<VoiceMeta>> {
+    let src = voices_dir.join(format!("{other}.wav"));
+    if fs::hard_link(&src, dest).is_err() {
+        fs::copy(&src, dest)
+            .with_context(|| format!("failed to copy {} → {}", src.display(), dest.display()))?;
+    }
+    Ok(read_meta(voices_dir, other))
+}
+
+/// Copy the reference audio to `dest` as 16-bit PCM, resampling it to the
+/// configured `voice_sample_rate` so every voice reaches the model at the
+/// same rate. Returns `None` if the file could not be read as WAV and was
+/// copied as-is.
+fn store_reference(cfg: &Config, src: &Path, dest: &Path) -> Result<Option<VoiceMeta>> {
+    // Stored as 16-bit PCM, since some backends can't read 24-bit or float
+    let mut temp = generate::TempWorkspace::new();
+    let src = match audio::non_pcm16_format(src) {
+        Some(format) => {
+            let stem = dest.file_stem().unwrap_or_default().to_string_lossy();
+            let converted = temp
+                .track(dest.with_file_name(format!(".{stem}.pcm16-{}.wav", std::process::id())));
+            output::status("Converting", &format!("{format} reference to 16-bit PCM"));
+            audio::to_pcm16(src, &converted)?;
+            converted
+        }
+        None => src.to_path_buf(),
+    };
+    let src = src.as_path();
+    let original = match audio::sample_rate(src) {
+        Ok(rate) => rate,
+        Err(e) => {
+            output::warn(&format!(
+                "could not read reference audio as WAV ({e:#}); stored unchanged"
+            ));
+            fs::copy(src, dest).with_context(|| {
+                format!("failed to copy {} → {}", src.display(), dest.display())
+            })?;
+            return Ok(None);
+        }
+    };
+
+    let target = cfg.voice_sample_rate;
+    if target == 0 || target == original {
+        fs::copy(src, dest)
+            .with_context(|| format!("failed to copy {} → {}", src.display(), dest.display()))?;
+        return Ok(Some(VoiceMeta {
+            original_sample_rate: original,
+            sample_rate: original,
+            content_hash: None,
+            model_variant: None,
+        }));
+    }
+
+    output::status("Resampling", &format!("{original} Hz → {target} Hz"));
+    audio::resample(src, dest, target)?;
+    Ok(Some(VoiceMeta {
+        original_sample_rate: original,
+        sample_rate: target,
+        content_hash: None,
+        model_variant: None,
+    }))
+}
+
+pub fn list(missing_transcripts: bool) -> Result<()> {
     let cfg = config::load_or_default();
     let voices_dir = config::expand_path(&cfg.voices_dir);
 
@@ -22,21 +162,29 @@ pub fn list() -> Result<()> {
         if path.extension().and_then(|e| e.to_str()) == Some("wav") {
             let name = path.file_stem().unwrap().to_string_lossy();
             let txt_path = path.with_extension("txt");
-            let transcript = if txt_path.exists() {
-                fs::read_to_string(&txt_path).unwrap_or_default()
-            } else {
-                "(no transcript)".to_string()
+            let transcript = fs::read_to_string(&txt_path)
+                .ok()
+                .filter(|t| !t.trim().is_empty());
+            let label = match read_meta(&voices_dir, &name).and_then(|m| m.model_variant) {
+                Some(variant) => format!("{} [{variant}]", name.green()),
+                None => name.green().to_string(),
             };
-            println!(
-                "  {} — {}",
-                name.green(),
-                transcript.trim().chars().take(60).collect::<String>()
-            );
+
+            match transcript {
+                Some(_) if missing_transcripts => continue,
+                Some(t) => println!(
+                    "  {label} — {}",
+                    t.trim().chars().take(60).collect::<String>()
+                ),
+                None => println!("  {label} — {}", "no transcript".yellow().bold()),
+            }
             found = true;
         }
     }
 
-    if !found {
+    if !found && missing_transcripts {
+        println!("All saved voices have transcripts.");
+    } else if !found {
         println!("No saved voices.");
         println!("Use `qwen-tts voices add <name> --ref <audio.wav>` to enroll one.");
     }
@@ -44,45 +192,582 @@ pub fn list() -> Result<()> {
     Ok(())
 }
 
-pub fn add(name: &str, ref_audio: &str, transcript: Option<&str>) -> Result<()> {
+/// Keys under which model configs list their preset speakers.
+const SPEAKER_KEYS: &[&str] = &["spk_id", "speaker_ids", "speakers"];
+
+/// Downloads only `config.json` of the repo in argv[1] and prints its path.
+const CONFIG_DOWNLOAD_SCRIPT: &str = "import sys; \
+    from huggingface_hub import hf_hub_download; \
+    print(hf_hub_download(sys.argv[1], 'config.json'))";
+
+/// Preset speaker names found anywhere in a model's `config.json`.
+fn speakers_in(value: &serde_json::Value) -> Option<Vec<String>> {
+    let object = value.as_object()?;
+    for key in SPEAKER_KEYS {
+        let names: Vec<String> = match object.get(*key) {
+            Some(serde_json::Value::Object(map)) => map.keys().cloned().collect(),
+            Some(serde_json::Value::Array(list)) => list
+                .iter()
+                .filter_map(|v| v.as_str().map(str::to_string))
+                .collect(),
+            _ => continue,
+        };
+        if !names.is_empty() {
+            return Some(names);
+        }
+    }
+    object.values().find_map(speakers_in)
+}
+
+/// ~/.qwen-tts/cache/builtin_voices.json: preset voices per model repo.
+fn builtin_cache_path() -> std::path::PathBuf {
+    config::base_dir().join("cache").join("builtin_voices.json")
+}
+
+type BuiltinCache = std::collections::BTreeMap<String, Vec<String>>;
+
+fn read_builtin_cache() -> BuiltinCache {
+    fs::read_to_string(builtin_cache_path())
+        .ok()
+        .and_then(|text| serde_json::from_str(&text).ok())
+        .unwrap_or_default()
+}
+
+/// Preset voices of the configured model without network access: from the
+/// installed model, else from the cache.
+pub(crate) fn cached_builtin_names(cfg: &Config) -> Vec<String> {
+    let Ok(repo) = models::repo_id(cfg.backend, &cfg.model_variant) else {
+        return Vec::new();
+    };
+    local_builtin_names(cfg)
+        .or_else(|| read_builtin_cache().remove(repo))
+        .unwrap_or_default()
+}
+
+fn local_builtin_names(cfg: &Config) -> Option<Vec<String>> {
+    let path = models::model_dir(cfg, &cfg.model_variant).join("config.json");
+    let text = fs::read_to_string(path).ok()?;
+    speakers_in(&serde_json::from_str(&text).ok()?)
+}
+
+/// Fetch the model's `config.json` from Hugging Face with the configured
+/// Python environment and read its preset voices.
+fn fetch_builtin_names(cfg: &Config, repo: &str) -> Result<Vec<String>> {
+    if !cfg.auto_download {
+        return Err(ErrorKind::Model.error(
+            "the model is not installed and downloads are disabled; \
+             install it with `qwen-tts models download` to list its voices",
+        ));
+    }
+    let python = config::expand_path(&cfg.python_path);
+    output::status("Fetching", &format!("{repo} config.json..."));
+    let result = Command::new(&python)
+        .args(["-c", CONFIG_DOWNLOAD_SCRIPT, repo])
+        .envs(cfg.network_env())
+        .envs(cfg.hf_auth_env())
+        .stderr(std::process::Stdio::inherit())
+        .output()
+        .with_context(|| format!("failed to run {}", python.display()))?;
+    if !result.status.success() {
+        return Err(ErrorKind::Model.error(format!(
+            "could not fetch config.json for {repo}; install the model with \
+             `qwen-tts models download` and try again"
+        )));
+    }
+    let path = String::from_utf8_lossy(&result.stdout).trim().to_string();
+    let text = fs::read_to_string(&path).with_context(|| format!("failed to read {path}"))?;
+    let value = serde_json::from_str(&text).with_context(|| format!("invalid JSON in {path}"))?;
+    Ok(speakers_in(&value).unwrap_or_default())
+}
+
+/// List the preset voices built into the configured model next to the
+/// enrolled ones. Results are cached per model repo; `refresh` re-reads them.
+pub fn builtin(refresh: bool) -> Result<()> {
+    let cfg = config::load_or_default();
+    let repo = models::repo_id(cfg.backend, &cfg.model_variant)?;
+
+    let mut cache = read_builtin_cache();
+    let names = match local_builtin_names(&cfg) {
+        Some(names) => names,
+        None => match cache.get(repo) {
+            Some(names) if !refresh => names.clone(),
+            _ => fetch_builtin_names(&cfg, repo)?,
+        },
+    };
+    if cache.get(repo) != Some(&names) {
+        cache.insert(repo.to_string(), names.clone());
+        let path = builtin_cache_path();
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent).ok();
+        }
+        let text = serde_json::to_string_pretty(&cache).context("failed to serialize cache")?;
+        fs::write(&path, text).ok();
+    }
+
+    println!("{} ({repo})", "Built-in voices".bold());
+    if names.is_empty() {
+        println!("  This model has no preset voices; clone or design one instead.");
+    }
+    for name in &names {
+        if name.eq_ignore_ascii_case(&cfg.default_voice) {
+            println!("  {} (default)", name.green());
+        } else {
+            println!("  {}", name.green());
+        }
+    }
+
+    let saved = saved_names(&cfg);
+    println!();
+    println!("{}", "Saved voices".bold());
+    if saved.is_empty() {
+        println!("  None. Use `qwen-tts voices add <name> --ref <audio.wav>` to enroll one.");
+    }
+    for name in &saved {
+        println!("  {}", name.green());
+    }
+    Ok(())
+}
+
+/// Names of the saved voices in `voices_dir`, sorted.
+pub(crate) fn saved_names(cfg: &Config) -> Vec<String> {
+    let voices_dir = config::expand_path(&cfg.voices_dir);
+    let mut names: Vec<String> = fs::read_dir(&voices_dir)
+        .into_iter()
+        .flatten()
+        .filter_map(|entry| entry.ok().map(|e| e.path()))
+        .filter(|path| path.extension().and_then(|e| e.to_str()) == Some("wav"))
+        .filter_map(|path| Some(path.file_stem()?.to_string_lossy().to_string()))
+        .collect();
+    names.sort();
+    names
+}
+
+/// Print saved voice names one per line, for shell completion. Never fails
+/// or initializes anything, so completing is always quiet.
+pub fn print_names() {
+    let cfg = config::read_existing().unwrap_or_default();
+    for name in saved_names(&cfg) {
+        println!("{name}");
+    }
+}
+
+/// Closest name to `name` among `candidates`, if any is near enough to be
+/// a likely typo. Matching ignores case.
+pub(crate) fn suggest<'a>(name: &str, candidates: &'a [String]) -> Option<&'a str> {
+    let name = name.to_lowercase();
+    let limit = (name.chars().count() / 3).max(1);
+    candidates
+        .iter()
+        .map(|c| (edit_distance(&name, &c.to_lowercase()), c))
+        .filter(|&(d, _)| d <= limit)
+        .min_by_key(|&(d, _)| d)
+        .map(|(_, c)| c.as_str())
+}
+
+/// Levenshtein distance between two strings, counted in characters.
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut prev: Vec<usize> = (0..=b.len()).collect();
+    for (i, ca) in a.chars().enumerate() {
+        let mut row = vec![i + 1];
+        for (j, &cb) in b.iter().enumerate() {
+            let cost = usize::from(ca != cb);
+            row.push((prev[j] + cost).min(prev[j + 1] + 1).min(row[j] + 1));
+        }
+        prev = row;
+    }
+    prev[b.len()]
+}
+
+/// Error for a saved voice that doesn't exist, suggesting a close match.
+pub(crate) fn not_found(cfg: &Config, name: &str) -> anyhow::Error {
+    let names = saved_names(cfg);
+    let hint = match suggest(name, &names) {
+        Some(close) => format!("; did you mean '{close}'?"),
+        None if names.is_empty() => {
+            "\nUse `qwen-tts voices add <name> --ref <audio.wav>` to enroll one".to_string()
+        }
+        None => format!("\nSaved voices: {}", names.join(", ")),
+    };
+    ErrorKind::Config.error(format!("voice '{name}' not found{hint}"))
+}
+
+/// Run speech recognition over `audio` using the configured Python environment.
+pub(crate) fn transcribe(cfg: &Config, audio: &Path) -> Result<String> {
+    let python = config::expand_path(&cfg.python_path);
+    let engine = match cfg.backend {
+        Backend::Mlx => "mlx-whisper",
+        Backend::Cuda | Backend::Cpu | Backend::Custom => "whisper",
+    };
+    output::status(
+        "Transcribing",
+        &format!("{} with {engine}...", audio.display()),
+    );
+
+    let out = Command::new(python.to_string_lossy().as_ref())
+        .args(["-c", TRANSCRIBE_SCRIPT])
+        .arg(audio)
+        .stderr(std::process::Stdio::inherit())
+        .output()
+        .context("failed to run Python for transcription")?;
+
+    if !out.status.success() {
+        return Err(ErrorKind::Backend.error(
+            "transcription failed (install mlx-whisper, openai-whisper, or transformers in the qwen-tts venv)",
+        ));
+    }
+
+    let stdout = String::from_utf8_lossy(&out.stdout);
+    let text = stdout.lines().last().unwrap_or_default().trim().to_string();
+    if text.is_empty() {
+        return Err(ErrorKind::Backend.error("transcription produced no text"));
+    }
+    Ok(text)
+}
+
+pub fn add(
+    name: &str,
+    ref_audio: &[String],
+    transcript: Option<&str>,
+    auto_transcript: bool,
+    variant: Option<&str>,
+) -> Result<()> {
     let cfg = config::load_or_default();
     let voices_dir = config::expand_path(&cfg.voices_dir);
-    fs::create_dir_all(&voices_dir)?;
+    config::ensure_writable(&voices_dir, "voices_dir")?;
+
+    let clips = generate::reference_clips(ref_audio)?;
+    let [src] = clips.as_slice() else {
+        // Several clips are stored as one reference, like `clone` merges them
+        let mut temp = generate::TempWorkspace::new();
+        let merged =
+            temp.track(voices_dir.join(format!(".{name}.merged-{}.wav", std::process::id())));
+        output::status(
+            "Merging",
+            &format!("{} reference clips into one...", clips.len()),
+        );
+        audio::merge_references(&clips, &merged)?;
+        return enroll(&cfg, name, &merged, transcript, auto_transcript, variant);
+    };
+    enroll(&cfg, name, src, transcript, auto_transcript, variant)
+}
 
-    let src = config::expand_path(ref_audio);
-    if !src.exists() {
-        anyhow::bail!("reference audio not found: {}", src.display());
+/// Store `src` as the voice `name`, with its transcript and preferred
+/// model variant.
+fn enroll(
+    cfg: &Config,
+    name: &str,
+    src: &Path,
+    transcript: Option<&str>,
+    auto_transcript: bool,
+    variant: Option<&str>,
+) -> Result<()> {
+    let voices_dir = config::expand_path(&cfg.voices_dir);
+    // Unreadable clips are reported when they are stored
+    let hash = audio::content_hash(src).ok();
+    let duplicate = hash
+        .as_deref()
+        .and_then(|hash| find_duplicate(cfg, name, hash));
+    let mut alias_of = None;
+    if let Some(other) = duplicate {
+        output::warn(&format!(
+            "this clip is already enrolled as the voice '{other}'"
+        ));
+        // Without a terminal to answer, store a separate copy
+        if std::io::stdin().is_terminal()
+            && output::prompt_yn(
+                &format!("Enroll '{name}' as an alias of '{other}' instead of storing it twice?"),
+                false,
+            )
+        {
+            alias_of = Some(other);
+        }
     }
+    // An alias without a transcript of its own shares the original's
+    let shared = alias_of
+        .as_ref()
+        .filter(|_| transcript.is_none())
+        .and_then(|other| fs::read_to_string(voices_dir.join(format!("{other}.txt"))).ok())
+        .filter(|t| !t.trim().is_empty());
+
+    let generated;
+    let transcript = match transcript {
+        Some(t) => Some(t),
+        None if shared.is_some() => shared.as_deref(),
+        None if auto_transcript => {
+            let draft = transcribe(cfg, src)?;
+            generated = editor::open_with("Review transcript", &draft)?
+                .context("transcript review cancelled; voice not enrolled")?;
+            Some(generated.as_str())
+        }
+        None => None,
+    };
 
     let dest_wav = voices_dir.join(format!("{name}.wav"));
-    fs::copy(&src, &dest_wav)
-        .with_context(|| format!("failed to copy {} → {}", src.display(), dest_wav.display()))?;
+    let meta_path = voices_dir.join(format!("{name}.toml"));
+    // Never write through a link shared with another voice
+    fs::remove_file(&dest_wav).ok();
+    let meta = match &alias_of {
+        Some(other) => link_reference(&voices_dir, other, &dest_wav)?,
+        None => store_reference(cfg, src, &dest_wav)?,
+    };
+    match meta {
+        Some(mut meta) => {
+            meta.content_hash = hash;
+            // An alias doesn't inherit the original's preferred variant
+            meta.model_variant = variant.map(str::to_string);
+            let text =
+                toml::to_string_pretty(&meta).context("failed to serialize voice metadata")?;
+            fs::write(&meta_path, text)
+                .with_context(|| format!("failed to write {}", meta_path.display()))?;
+        }
+        None => {
+            fs::remove_file(&meta_path).ok();
+            if variant.is_some() {
+                output::warn("the model variant is only recorded for WAV references; not saved");
+            }
+        }
+    }
 
     if let Some(t) = transcript {
         let dest_txt = voices_dir.join(format!("{name}.txt"));
         fs::write(&dest_txt, t)?;
+        if let Ok(secs) = audio::duration_secs(&dest_wav) {
+            check_transcript_length(t, secs);
+        }
     }
 
-    println!("{} Voice '{}' enrolled.", "Done!".green().bold(), name);
+    match alias_of {
+        Some(other) => println!(
+            "{} Voice '{}' enrolled as an alias of '{}'.",
+            "Done!".green().bold(),
+            name,
+            other
+        ),
+        None => println!("{} Voice '{}' enrolled.", "Done!".green().bold(), name),
+    }
     Ok(())
 }
 
+/// Plausible speaking rates in words per second; natural speech is around
+/// 2-3, so anything outside this range means the transcript and audio
+/// probably don't belong together.
+pub(crate) const PLAUSIBLE_WORDS_PER_SEC: std::ops::RangeInclusive<f64> = 0.5..=5.0;
+
+/// Rough word count of a transcript. Scripts written without spaces (CJK)
+/// count two characters per word.
+pub(crate) fn transcript_words(text: &str) -> f64 {
+    text.split_whitespace()
+        .map(|token| {
+            let cjk = token
+                .chars()
+                .filter(|c| matches!(u32::from(*c), 0x3040..=0x30FF | 0x3400..=0x9FFF | 0xAC00..=0xD7AF))
+                .count();
+            let other = token.chars().any(|c| c.is_alphanumeric()) && cjk < token.chars().count();
+            cjk as f64 / 2.0 + f64::from(u8::from(other))
+        })
+        .sum()
+}
+
+/// Warn when a transcript is far too long or short for `secs` of audio,
+/// which usually means it was pasted for a different clip.
+fn check_transcript_length(transcript: &str, secs: f64) {
+    let words = transcript_words(transcript);
+    if words == 0.0 || secs <= 0.0 {
+        return;
+    }
+    let rate = words / secs;
+    if !PLAUSIBLE_WORDS_PER_SEC.contains(&rate) {
+        output::warn(&format!(
+            "the transcript has about {words:.0} words for {secs:.1}s of audio ({rate:.1} words/s; \
+             natural speech is 2-3). Check that it matches the reference audio, or fix it with \
+             `qwen-tts voices add` again"
+        ));
+    }
+}
+
+/// Media file extensions that ffmpeg can read straight from a URL; anything
+/// else (video pages, share links) goes through yt-dlp.
+#[cfg(feature = "url-import")]
+const DIRECT_MEDIA_EXTENSIONS: &[&str] = &[
+    "wav", "mp3", "flac", "ogg", "opus", "m4a", "aac", "mp4", "webm", "mkv", "mov",
+];
+
+/// Reference clips much longer than this are usually a whole video.
+#[cfg(feature = "url-import")]
+const LONG_REFERENCE_SECS: f64 = 30.0;
+
+/// Enroll a voice from a remote media URL: download it, extract a WAV, then
+/// continue as `add` does.
+#[cfg(feature = "url-import")]
+pub fn add_from_url(
+    name: &str,
+    url: &str,
+    transcript: Option<&str>,
+    auto_transcript: bool,
+    variant: Option<&str>,
+) -> Result<()> {
+    if crate::platform::find_in_path("ffmpeg").is_none() {
+        return Err(ErrorKind::Backend.error(
+            "ffmpeg is required for --url; install it or download the audio and use --ref",
+        ));
+    }
+
+    let mut temp = generate::TempWorkspace::new();
+    let work = temp.track(
+        config::base_dir()
+            .join("tmp")
+            .join(format!("url-{}", std::process::id())),
+    );
+    fs::create_dir_all(&work).with_context(|| format!("failed to create {}", work.display()))?;
+
+    let cfg = config::load_or_default();
+    let wav = fetch_reference(&cfg, url, &work)?;
+    let secs = crate::audio::duration_secs(&wav)?;
+    if secs > LONG_REFERENCE_SECS {
+        output::warn(&format!(
+            "downloaded audio is {secs:.0}s long; a clean 5-15s clip clones best"
+        ));
+    }
+    add(
+        name,
+        &[wav.to_string_lossy().to_string()],
+        transcript,
+        auto_transcript,
+        variant,
+    )
+}
+
+/// Download `url` into `work` and convert it to `work/reference.wav`.
+#[cfg(feature = "url-import")]
+fn fetch_reference(cfg: &Config, url: &str, work: &Path) -> Result<std::path::PathBuf> {
+    let wav = work.join("reference.wav");
+    let direct = url
+        .split(['?', '#'])
+        .next()
+        .and_then(|path| path.rsplit_once('.'))
+        .is_some_and(|(_, ext)| DIRECT_MEDIA_EXTENSIONS.contains(&ext.to_lowercase().as_str()));
+
+    let source = if direct {
+        output::status("Downloading", url);
+        url.to_string()
+    } else {
+        if crate::platform::find_in_path("yt-dlp").is_none() {
+            return Err(ErrorKind::Backend.error(
+                "yt-dlp is required to fetch audio from this URL; install it, \
+                 or pass a direct link to a media file",
+            ));
+        }
+        output::status("Downloading", &format!("{url} via yt-dlp..."));
+        let status = Command::new("yt-dlp")
+            .args([
+                "--no-playlist",
+                "--quiet",
+                "--progress",
+                "-f",
+                "bestaudio/best",
+                "-o",
+            ])
+            .arg(work.join("source.%(ext)s"))
+            .arg(url)
+            .envs(cfg.network_env())
+            .status()
+            .context("failed to run yt-dlp")?;
+        if !status.success() {
+            return Err(ErrorKind::Backend.error(format!("yt-dlp failed to download {url}")));
+        }
+        let downloaded = fs::read_dir(work)?
+            .filter_map(|e| e.ok())
+            .map(|e| e.path())
+            .find(|p| p.file_stem().is_some_and(|s| s == "source"))
+            .context("yt-dlp produced no file")?;
+        downloaded.to_string_lossy().to_string()
+    };
+
+    output::status("Extracting", "audio to WAV...");
+    let status = Command::new("ffmpeg")
+        .args(["-y", "-hide_banner", "-loglevel", "error", "-i"])
+        .arg(&source)
+        .arg("-vn")
+        .arg(&wav)
+        .envs(cfg.network_env())
+        .status()
+        .context("failed to run ffmpeg")?;
+    if !status.success() {
+        return Err(ErrorKind::Backend.error(format!("ffmpeg could not extract audio from {url}")));
+    }
+    Ok(wav)
+}
+
+/// Sentence used by `voices preview`; covers every letter and a mix of sounds.
+const PREVIEW_TEXT: &str =
+    "The quick brown fox jumps over the lazy dog, while five wizards quietly hum a jazzy tune.";
+
+/// Synthesize a fixed sentence with a saved voice and play it.
+pub fn preview(name: &str, text: Option<&str>) -> Result<()> {
+    let cfg = config::load()?;
+    let actual = generate::clone_to_file(
+        &cfg,
+        &generate::CloneArgs {
+            ref_audio: Vec::new(),
+            ref_text: None,
+            auto_transcript: false,
+            voice: Some(name.to_string()),
+            text: Some(text.unwrap_or(PREVIEW_TEXT).to_string()),
+            file: Vec::new(),
+            speed: None,
+            pitch: None,
+            output: None,
+            format: None,
+            append: None,
+            no_join: false,
+            variant: None,
+            normalize: normalize::Options::default(),
+            post: generate::PostProcess::default(),
+        },
+    )?;
+    generate::play_audio(&cfg, &actual)
+}
+
 pub fn remove(name: &str) -> Result<()> {
     let cfg = config::load_or_default();
     let voices_dir = config::expand_path(&cfg.voices_dir);
 
     let wav = voices_dir.join(format!("{name}.wav"));
-    let txt = voices_dir.join(format!("{name}.txt"));
 
     if !wav.exists() {
-        anyhow::bail!("voice '{name}' not found");
+        return Err(not_found(&cfg, name));
     }
 
     fs::remove_file(&wav)?;
-    if txt.exists() {
-        fs::remove_file(&txt)?;
+    for ext in ["txt", "toml"] {
+        let path = voices_dir.join(format!("{name}.{ext}"));
+        if path.exists() {
+            fs::remove_file(&path)?;
+        }
     }
 
     println!("{} Voice '{}' removed.", "Done!".green().bold(), name);
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn suggests_close_names() {
+        let names: Vec<String> = ["Vivian", "Ryan", "narrator"]
+            .iter()
+            .map(|s| s.to_string())
+            .collect();
+        assert_eq!(edit_distance("kitten", "sitting"), 3);
+        assert_eq!(suggest("vivan", &names), Some("Vivian"));
+        assert_eq!(suggest("RYAN", &names), Some("Ryan"));
+        assert_eq!(suggest("narator", &names), Some("narrator"));
+        // Short names allow one edit, longer ones a third of their length
+        assert_eq!(suggest("Bob", &names), None);
+        assert_eq!(suggest("nrrtr", &names), None);
+        assert_eq!(suggest("anything", &[]), None);
+    }
+}
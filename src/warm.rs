@@ -0,0 +1,321 @@
+//! Reuse of a loaded CUDA/CPU backend between invocations (`keep_warm`).
+//!
+//! After a generation the compat script keeps serving on a Unix socket in
+//! `~/.qwen-tts/warm/` until it has been idle for `keep_warm` seconds. The
+//! next run with the same Python, script, and model sends its request there
+//! instead of loading the model again; if nothing answers, a new server is
+//! started, and if that fails the backend runs cold as usual. Starting a
+//! server holds a lock file, so parallel jobs share one server instead of
+//! each loading the model.
+
+use anyhow::Result;
+
+use crate::config::Config;
+use crate::generate::TtsParams;
+
+/// Run one generation on a warm backend process. `None` means the pool
+/// doesn't apply or couldn't start, and the backend should run cold.
+#[cfg(unix)]
+pub(crate) fn generate(cfg: &Config, params: &TtsParams) -> Option<Result<()>> {
+    unix::generate(cfg, params)
+}
+
+/// Unix sockets are required; other platforms always run cold.
+#[cfg(not(unix))]
+pub(crate) fn generate(_cfg: &Config, _params: &TtsParams) -> Option<Result<()>> {
+    None
+}
+
+#[cfg(unix)]
+mod unix {
+    use std::fs;
+    use std::io::{BufRead, BufReader, Read, Seek, SeekFrom, Write};
+    use std::os::fd::AsRawFd;
+    use std::os::unix::fs::PermissionsExt;
+    use std::os::unix::net::UnixStream;
+    use std::os::unix::process::CommandExt;
+    use std::path::Path;
+    use std::process::{Command, Stdio};
+    use std::time::{Duration, Instant};
+
+    use anyhow::{Context, Result};
+    use serde::Deserialize;
+
+    use crate::config::{self, Config};
+    use crate::error::ErrorKind;
+    use crate::generate::{self, TtsParams};
+    use crate::output;
+    use crate::platform::Backend;
+
+    /// Longest socket path every Unix accepts (macOS allows 104 bytes).
+    const MAX_SOCKET_PATH: usize = 100;
+
+    /// How long a new server may take to load the model when no
+    /// `generation_timeout` is set.
+    const STARTUP_LIMIT: Duration = Duration::from_secs(600);
+
+    #[derive(Deserialize)]
+    struct Reply {
+        ok: bool,
+        #[serde(default)]
+        error: Option<String>,
+    }
+
+    pub(super) fn generate(cfg: &Config, params: &TtsParams) -> Option<Result<()>> {
+        let secs = cfg.keep_warm.filter(|&secs| secs > 0)?;
+        // The compat script only writes one joined file
+        if !matches!(cfg.backend, Backend::Cuda | Backend::Cpu) || !params.join {
+            return None;
+        }
+        let python = config::expand_path(&cfg.python_path);
+        let (script, model) = match (generate::compat_script(cfg), generate::model_id(cfg)) {
+            (Ok(script), Ok(model)) => (script, model),
+            (Err(e), _) | (_, Err(e)) => return Some(Err(e)),
+        };
+
+        let key = format!(
+            "{}\0{}\0{}\0{}",
+            python.display(),
+            script.display(),
+            model,
+            cfg.backend
+        );
+        let socket = config::base_dir()
+            .join("warm")
+            .join(format!("{:016x}.sock", fnv1a(key.as_bytes())));
+        if socket.as_os_str().len() > MAX_SOCKET_PATH {
+            output::verbose(
+                "Warm",
+                &format!(
+                    "{} is too long for a socket; running cold",
+                    socket.display()
+                ),
+            );
+            return None;
+        }
+        let log = socket.with_extension("log");
+
+        let stream = match connect_or_start(cfg, &python, &script, &model, &socket, &log, secs) {
+            Ok(Some(stream)) => stream,
+            Ok(None) => return None,
+            Err(e) => return Some(Err(e)),
+        };
+        Some(request(cfg, stream, params, &log))
+    }
+
+    /// Connect to the server on `socket`, starting it if none answers.
+    /// Only one process starts a server at a time; the others wait for it
+    /// and then connect.
+    fn connect_or_start(
+        cfg: &Config,
+        python: &Path,
+        script: &Path,
+        model: &str,
+        socket: &Path,
+        log: &Path,
+        secs: u64,
+    ) -> Result<Option<UnixStream>> {
+        if let Ok(stream) = UnixStream::connect(socket) {
+            output::verbose("Warm", "reusing the loaded backend");
+            return Ok(Some(stream));
+        }
+        let dir = socket.parent().expect("socket path has a parent");
+        fs::create_dir_all(dir).with_context(|| format!("failed to create {}", dir.display()))?;
+        // The socket accepts any request, including where to write files
+        fs::set_permissions(dir, fs::Permissions::from_mode(0o700))
+            .with_context(|| format!("failed to restrict {}", dir.display()))?;
+
+        let _lock = lock_file(&socket.with_extension("lock"))?;
+        // Another job may have started it while this one waited
+        if let Ok(stream) = UnixStream::connect(socket) {
+            output::verbose("Warm", "reusing the backend another job loaded");
+            return Ok(Some(stream));
+        }
+        start_server(cfg, python, script, model, socket, log, secs)
+    }
+
+    /// Open `path` and hold an exclusive lock on it until the file is
+    /// dropped.
+    fn lock_file(path: &Path) -> Result<fs::File> {
+        let file = fs::OpenOptions::new()
+            .create(true)
+            .truncate(false)
+            .write(true)
+            .open(path)
+            .with_context(|| format!("failed to open {}", path.display()))?;
+        // SAFETY: the descriptor is valid for as long as `file` lives
+        if unsafe { libc::flock(file.as_raw_fd(), libc::LOCK_EX) } != 0 {
+            return Err(std::io::Error::last_os_error())
+                .with_context(|| format!("failed to lock {}", path.display()));
+        }
+        Ok(file)
+    }
+
+    /// Start the compat script in server mode, detached from this process,
+    /// and wait until it accepts connections. `Ok(None)` if it exited first.
+    fn start_server(
+        cfg: &Config,
+        python: &Path,
+        script: &Path,
+        model: &str,
+        socket: &Path,
+        log: &Path,
+        secs: u64,
+    ) -> Result<Option<UnixStream>> {
+        let log_file =
+            fs::File::create(log).with_context(|| format!("failed to create {}", log.display()))?;
+
+        output::status(
+            "Warm",
+            &format!("loading the model into a backend that stays loaded for {secs}s"),
+        );
+        let mut child = Command::new(python)
+            .arg(script)
+            .args(["--model", model])
+            .arg("--serve")
+            .arg(socket)
+            .args(["--keep_warm", &secs.to_string()])
+            .envs(cfg.network_env())
+            .envs(cfg.hf_auth_env())
+            .stdin(Stdio::null())
+            .stdout(log_file.try_clone()?)
+            .stderr(log_file)
+            // Its own process group, so Ctrl+C here doesn't stop it
+            .process_group(0)
+            .spawn()
+            .map_err(|e| {
+                ErrorKind::Backend.error(format!(
+                    "failed to run TTS command {}: {e}",
+                    python.display()
+                ))
+            })?;
+
+        let limit = cfg
+            .generation_timeout
+            .filter(|&secs| secs > 0)
+            .map_or(STARTUP_LIMIT, Duration::from_secs);
+        let deadline = Instant::now() + limit;
+        loop {
+            if let Ok(stream) = UnixStream::connect(socket) {
+                return Ok(Some(stream));
+            }
+            if child.try_wait().ok().flatten().is_some() {
+                output::warn(&format!(
+                    "the warm backend exited before it was ready (see {}); running it cold",
+                    log.display()
+                ));
+                return Ok(None);
+            }
+            if Instant::now() >= deadline {
+                child.kill().ok();
+                child.wait().ok();
+                return Err(ErrorKind::Backend.error(format!(
+                    "the warm backend was not ready after {}s and was stopped (see {})",
+                    limit.as_secs(),
+                    log.display()
+                )));
+            }
+            std::thread::sleep(Duration::from_millis(200));
+        }
+    }
+
+    /// Send one generation request and wait for the server's reply. The
+    /// server's output for the request is shown with `--verbose` and, on
+    /// failure, diagnosed like a cold run's.
+    fn request(cfg: &Config, stream: UnixStream, params: &TtsParams, log: &Path) -> Result<()> {
+        let start = fs::metadata(log).map_or(0, |m| m.len());
+        let result = send(cfg, stream, params, log);
+        let output = log_since(log, start);
+        if output::is_verbose() && !cfg.quiet_backend {
+            eprint!("{output}");
+        }
+        match result {
+            Ok(Ok(())) => Ok(()),
+            Ok(Err(failure)) => Err(generate::backend_failure(
+                cfg,
+                &format!("{output}{failure}\n(warm backend log: {})", log.display()),
+            )),
+            Err(e) => {
+                output::record_backend_stderr(&output);
+                Err(e)
+            }
+        }
+    }
+
+    /// The tail of what the server wrote to `log` after byte `start`.
+    fn log_since(log: &Path, start: u64) -> String {
+        let Ok(mut file) = fs::File::open(log) else {
+            return String::new();
+        };
+        let end = file.metadata().map_or(start, |m| m.len());
+        let from = start.max(end.saturating_sub(generate::STDERR_TAIL_BYTES as u64));
+        let mut bytes = Vec::new();
+        if file.seek(SeekFrom::Start(from)).is_ok() {
+            file.take(end.saturating_sub(from))
+                .read_to_end(&mut bytes)
+                .ok();
+        }
+        String::from_utf8_lossy(&bytes).into_owned()
+    }
+
+    /// Write the request and read the reply. `Ok(Err(message))` when the
+    /// server reports that generation failed or stops answering.
+    fn send(
+        cfg: &Config,
+        mut stream: UnixStream,
+        params: &TtsParams,
+        log: &Path,
+    ) -> Result<std::result::Result<(), String>> {
+        // The server may have been started from another working directory
+        let absolute = |path: &Path| std::path::absolute(path).unwrap_or_else(|_| path.into());
+        let line = serde_json::json!({
+            "text": params.text,
+            "instruct": params.instruct,
+            "voice": params.voice,
+            "speed": params.speed,
+            "ref_audio": params.ref_audio.map(|p| absolute(Path::new(p))),
+            "ref_text": params.ref_text,
+            "output_path": absolute(params.output_path),
+        })
+        .to_string();
+
+        let timeout = cfg
+            .generation_timeout
+            .filter(|&secs| secs > 0)
+            .map(Duration::from_secs);
+        stream.set_read_timeout(timeout).ok();
+        stream
+            .write_all(format!("{line}\n").as_bytes())
+            .context("failed to send the request to the warm backend")?;
+
+        let mut reply = String::new();
+        let read = BufReader::new(&stream).read_line(&mut reply);
+        let lost = |what: &str| {
+            ErrorKind::Backend.error(format!("{what}; see {} for its output", log.display()))
+        };
+        match read {
+            Ok(0) => return Ok(Err("the warm backend stopped during generation".to_string())),
+            Ok(_) => {}
+            Err(e) if e.kind() == std::io::ErrorKind::WouldBlock => {
+                return Err(lost(&format!(
+                    "generation timed out after {}s",
+                    timeout.unwrap_or_default().as_secs()
+                )));
+            }
+            Err(e) => return Err(lost(&format!("lost the warm backend ({e})"))),
+        }
+        let reply: Reply = serde_json::from_str(&reply)
+            .map_err(|_| lost("the warm backend sent an invalid reply"))?;
+        if reply.ok {
+            return Ok(Ok(()));
+        }
+        Ok(Err(reply.error.unwrap_or_default()))
+    }
+
+    /// Stable 64-bit FNV-1a hash, for socket names that survive upgrades.
+    fn fnv1a(bytes: &[u8]) -> u64 {
+        bytes.iter().fold(0xcbf2_9ce4_8422_2325, |hash, &b| {
+            (hash ^ u64::from(b)).wrapping_mul(0x0100_0000_01b3)
+        })
+    }
+}